@@ -0,0 +1,94 @@
+use std::fmt;
+
+use crate::game::Game;
+use crate::solver;
+
+/// Struct to represent how much one guess narrowed down the answer, and what the solver would
+/// have played in its place
+#[derive(Debug, PartialEq)]
+pub struct GuessAnalysis {
+    /// The guess that was played
+    pub guess: String,
+    /// The number of words that could be the answer before the guess
+    pub before: usize,
+    /// The number of words that could be the answer after the guess
+    pub after: usize,
+    /// The information the guess gave, in bits
+    pub bits: f64,
+    /// The guess the solver would have played instead, if there was one
+    pub suggestion: Option<String>,
+}
+
+impl fmt::Display for GuessAnalysis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}  {:>5} → {:<5} words  {:>4.1} bits",
+            self.guess.to_uppercase(),
+            self.before,
+            self.after,
+            self.bits
+        )?;
+
+        match &self.suggestion {
+            Some(suggestion) if *suggestion == self.guess => write!(f, "  same as the solver"),
+            Some(suggestion) => write!(f, "  solver: {}", suggestion.to_uppercase()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Returns a breakdown of every guess in a game, counting the words from the game's word list
+/// that were still possible before and after each one
+///
+/// Parameters
+/// game:   The game to analyse
+pub fn analyse(game: &Game) -> Vec<GuessAnalysis> {
+    let words = game.words();
+    let mut candidates = words.to_vec();
+    let mut analysis: Vec<GuessAnalysis> = Vec::new();
+
+    for word in game.guesses() {
+        let suggestion = solver::suggest(words, &candidates);
+        let before = candidates.len();
+
+        candidates.retain(|candidate| solver::is_consistent(candidate, word));
+
+        analysis.push(GuessAnalysis {
+            guess: word.to_string(),
+            before,
+            after: candidates.len(),
+            bits: solver::bits_gained(before, candidates.len()),
+            suggestion,
+        });
+    }
+
+    analysis
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Checks if each guess narrows down the words left from the one before, ending on the answer
+    fn analysis_follows_each_guess() {
+        let words: Vec<String> = ["cigar", "crane", "moist", "clear", "cider", "civic"]
+            .iter()
+            .map(|word| word.to_string())
+            .collect();
+
+        let mut game = Game::new(String::from("cigar"), words, 6, false);
+        game.guess("moist").unwrap();
+        game.guess("cigar").unwrap();
+
+        let analysis = analyse(&game);
+
+        assert!(analysis.len() == 2);
+        assert!(analysis[0].before == 6);
+        assert!(analysis[1].before == analysis[0].after);
+        assert!(analysis[1].after == 1);
+        assert!(analysis[0].bits == solver::bits_gained(6, analysis[0].after));
+        assert!(analysis[0].suggestion.is_some());
+    }
+}
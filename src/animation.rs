@@ -0,0 +1,155 @@
+use std::time::Duration;
+
+use crate::scoring::Scoring;
+use crate::theme::Theme;
+use crate::word::Word;
+
+/// How long each frame of an animation is shown for
+pub const FRAME_DELAY: Duration = Duration::from_millis(90);
+
+/// How far the prompt moves in each frame of the shake animation
+const SHAKE_OFFSETS: [usize; 6] = [2, 0, 2, 0, 1, 0];
+
+/// Enum for the effects that can be played on the game while it is drawn full screen
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Effect {
+    /// The latest guess being flipped over one box at a time. Boxes before `revealed` are shown
+    /// with their colours, and the box at `revealed` is shown half way through flipping
+    Reveal { revealed: usize },
+    /// The prompt being moved to the right by `offset` columns, after an invalid guess
+    Shake { offset: usize },
+    /// The box at `tile` in the winning guess jumping up
+    Bounce { tile: usize },
+}
+
+/// Returns the frames of the reveal animation for a guess
+///
+/// Parameters
+/// word_length:    The number of boxes in the guess
+pub fn reveal_frames(word_length: usize) -> Vec<Effect> {
+    (0..word_length)
+        .map(|revealed| Effect::Reveal { revealed })
+        .collect()
+}
+
+/// Returns the frames of the shake animation for an invalid guess
+pub fn shake_frames() -> Vec<Effect> {
+    SHAKE_OFFSETS
+        .iter()
+        .map(|&offset| Effect::Shake { offset })
+        .collect()
+}
+
+/// Returns the frames of the bounce animation for the winning guess
+///
+/// Parameters
+/// word_length:    The number of boxes in the guess
+pub fn bounce_frames(word_length: usize) -> Vec<Effect> {
+    (0..word_length)
+        .map(|tile| Effect::Bounce { tile })
+        .collect()
+}
+
+/// Returns the rows of a guess part way through an effect. Effects that don't change the guess
+/// return the guess as it is normally drawn
+///
+/// Parameters
+/// word:       The guess being animated
+/// theme:      The theme to style the letters with
+/// effect:     The frame of the effect to draw
+pub fn animated_rows(word: &Word, theme: &Theme, effect: &Effect) -> Vec<String> {
+    // Words scored by their pegs have no coloured boxes to animate
+    if word.scoring() == Scoring::Mastermind {
+        return word.rows(theme);
+    }
+
+    let mut rows = vec![String::new(), String::new(), String::new()];
+
+    for (i, letter) in word.letters().iter().enumerate() {
+        let boxes: [String; 3] = match effect {
+            // Letters that haven't flipped yet look like they did while they were being typed
+            Effect::Reveal { revealed } if i > *revealed => [
+                String::from("╭───╮"),
+                format!("│ {} │", letter.letter().to_uppercase()),
+                String::from("╰───╯"),
+            ],
+            // Half way through a flip the box is seen side on, so it is drawn flattened
+            Effect::Reveal { revealed } if i == *revealed => [
+                String::from("     "),
+                String::from("╭───╮"),
+                String::from("╰───╯"),
+            ],
+            // A bouncing box is drawn one row higher, with its top edge hidden behind the row
+            // above it
+            Effect::Bounce { tile } if i == *tile => [
+                letter.get_row(1, theme).unwrap(),
+                letter.get_row(2, theme).unwrap(),
+                String::from("     "),
+            ],
+            _ => [
+                letter.get_row(0, theme).unwrap(),
+                letter.get_row(1, theme).unwrap(),
+                letter.get_row(2, theme).unwrap(),
+            ],
+        };
+
+        for (row, letter_box) in rows.iter_mut().zip(boxes) {
+            row.push_str(&letter_box);
+        }
+    }
+
+    rows
+}
+
+/// Returns the compact row of a guess part way through an effect. Only the reveal animation
+/// changes the compact row, as there is no room for the others
+///
+/// Parameters
+/// word:       The guess being animated
+/// theme:      The theme to style the letters with
+/// effect:     The frame of the effect to draw
+pub fn animated_compact_row(word: &Word, theme: &Theme, effect: &Effect) -> String {
+    if word.scoring() == Scoring::Mastermind {
+        return word.compact_row(theme);
+    }
+
+    let mut row = String::new();
+
+    for (i, letter) in word.letters().iter().enumerate() {
+        match effect {
+            Effect::Reveal { revealed } if i > *revealed => {
+                row.push_str(&format!(" {} ", letter.letter().to_uppercase()))
+            }
+            Effect::Reveal { revealed } if i == *revealed => row.push_str(" - "),
+            _ => row.push_str(&letter.get_compact(theme)),
+        }
+    }
+
+    row
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Checks if the reveal animation shows letters that haven't flipped yet without colour, and
+    /// flattens the box that is flipping
+    fn reveal_flattens_flipping_box() {
+        let word_list = vec![String::from("crane")];
+        let word = Word::new(String::from("crane"), "crane", &word_list).unwrap();
+
+        let rows = animated_rows(&word, &Theme::default(), &Effect::Reveal { revealed: 3 });
+
+        assert!(rows[0].ends_with("     ╭───╮"));
+        assert!(rows[1].ends_with("╭───╮│ E │"));
+    }
+
+    #[test]
+    /// Checks if every animation has at least one frame to draw
+    fn animations_have_frames() {
+        assert!(reveal_frames(5).len() == 5);
+        assert!(bounce_frames(5).len() == 5);
+        assert!(!shake_frames().is_empty());
+    }
+}
@@ -0,0 +1,121 @@
+/// The key the bytes of a word are mixed with, so that codes don't give the word away at a glance
+const KEY: &[u8] = b"wordle_cli";
+
+/// The characters codes are written with. Letters that are easily mistaken for numbers are left
+/// out, so codes can be read out or typed by hand
+const ALPHABET: &[u8; 32] = b"0123456789abcdefghjkmnpqrstvwxyz";
+
+/// Returns a short code for a word, which can be shared to challenge someone to guess it. The code
+/// ends with a checksum, so codes with typos in them are caught when they are decoded
+///
+/// Parameters
+/// word:   The word to turn into a code
+pub fn encode(word: &str) -> String {
+    let mut bytes: Vec<u8> = word.to_lowercase().into_bytes();
+    bytes.push(checksum(&bytes));
+
+    // Each byte is mixed with the key and the byte before it, so repeated letters don't give
+    // repeated characters in the code
+    let mut previous = 0;
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte ^= KEY[i % KEY.len()] ^ previous;
+        previous = *byte;
+    }
+
+    // Writing out the bytes five bits at a time
+    let mut code = String::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for byte in bytes {
+        buffer = (buffer << 8) | u32::from(byte);
+        bits += 8;
+
+        while bits >= 5 {
+            bits -= 5;
+            code.push(ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+
+    if bits > 0 {
+        code.push(ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+
+    code
+}
+
+/// Returns the word a code was made from, or None if it isn't a valid code
+///
+/// Parameters
+/// code:   The code to decode, which can be in any case
+pub fn decode(code: &str) -> Option<String> {
+    let mut bytes: Vec<u8> = Vec::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for character in code.trim().to_lowercase().chars() {
+        let value = ALPHABET.iter().position(|&c| c as char == character)?;
+
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+
+    // Undoing the mixing, which depends on the mixed byte before each one
+    let mut previous = 0;
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        let mixed = *byte;
+        *byte ^= KEY[i % KEY.len()] ^ previous;
+        previous = mixed;
+    }
+
+    let expected = bytes.pop()?;
+    if bytes.is_empty() || checksum(&bytes) != expected {
+        return None;
+    }
+
+    String::from_utf8(bytes).ok()
+}
+
+/// Returns a single byte summing up the bytes of a word
+///
+/// Parameters
+/// bytes:  The bytes to sum up
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes
+        .iter()
+        .fold(0, |sum: u8, byte| sum.wrapping_mul(31).wrapping_add(*byte))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Checks if words are decoded back from their codes, whatever case the code is typed in
+    fn codes_decode_to_their_word() {
+        for word in ["cigar", "crane", "abbey", "strength"] {
+            let code = encode(word);
+
+            assert!(decode(&code) == Some(word.to_string()));
+            assert!(decode(&code.to_uppercase()) == Some(word.to_string()));
+            assert!(!code.contains(word));
+        }
+    }
+
+    #[test]
+    /// Checks if codes with typos in them are rejected instead of giving another word
+    fn mistyped_codes_are_rejected() {
+        let code = encode("cigar");
+        let mut mistyped: Vec<char> = code.chars().collect();
+        mistyped[2] = if mistyped[2] == 'a' { 'b' } else { 'a' };
+
+        assert!(decode(&mistyped.into_iter().collect::<String>()).is_none());
+        assert!(decode("not a code!").is_none());
+        assert!(decode("").is_none());
+    }
+}
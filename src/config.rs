@@ -0,0 +1,410 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use serde::Deserialize;
+
+use crate::difficulty::Difficulty;
+use crate::protocol::Protocol;
+use crate::scoring::Scoring;
+use crate::theme::{Palette, Theme};
+use crate::{AnswerChoice, Errors, GameOptions};
+
+/// The number of guesses the player gets if no other number is configured
+pub const DEFAULT_MAX_GUESSES: usize = 6;
+
+/// The number of hints the player gets if no other number is configured, so hints are off unless
+/// they are asked for
+pub const DEFAULT_HINTS: usize = 0;
+
+/// The length of the words to guess if no other length is configured
+pub const DEFAULT_WORD_LENGTH: usize = 5;
+
+/// Enum for where the value of a setting came from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    ConfigFile,
+    CommandLine,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::ConfigFile => write!(f, "config file"),
+            Source::CommandLine => write!(f, "command line"),
+        }
+    }
+}
+
+/// Struct to represent the value of a setting along with where it came from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Setting<T> {
+    pub value: T,
+    pub source: Source,
+}
+
+impl<T> Setting<T> {
+    /// Returns the setting from the highest priority place it was set in, with the command line
+    /// overriding the config file, and the config file overriding the default
+    ///
+    /// Parameters
+    /// default:        The value to use if the setting wasn't set anywhere
+    /// config_file:    The value from the config file, if there was one
+    /// command_line:   The value from the command line, if there was one
+    fn resolve(default: T, config_file: Option<T>, command_line: Option<T>) -> Setting<T> {
+        match (command_line, config_file) {
+            (Some(value), _) => Setting {
+                value,
+                source: Source::CommandLine,
+            },
+            (None, Some(value)) => Setting {
+                value,
+                source: Source::ConfigFile,
+            },
+            (None, None) => Setting {
+                value: default,
+                source: Source::Default,
+            },
+        }
+    }
+}
+
+/// Struct to represent a set of settings where any of them can be left out, as read from the
+/// config file or given on the command line
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct PartialSettings {
+    pub word_list: Option<String>,
+    pub definitions: Option<String>,
+    pub theme: Option<Palette>,
+    pub symbols: Option<bool>,
+    pub animations: Option<bool>,
+    pub stopwatch: Option<bool>,
+    pub analysis: Option<bool>,
+    pub compact: Option<bool>,
+    pub hard_mode: Option<bool>,
+    pub scoring: Option<Scoring>,
+    pub max_guesses: Option<usize>,
+    pub hints: Option<usize>,
+    pub difficulty: Option<Difficulty>,
+    pub word_length: Option<usize>,
+}
+
+impl PartialSettings {
+    /// Parses the settings out of the contents of a TOML config file
+    ///
+    /// Parameters
+    /// contents:   The contents of the config file
+    pub fn parse(contents: &str) -> Result<PartialSettings, Errors> {
+        let settings: PartialSettings = match toml::from_str(contents) {
+            Ok(settings) => settings,
+            Err(err) => return Err(Errors::InvalidConfig(err.message().to_string())),
+        };
+
+        if settings.max_guesses == Some(0) {
+            return Err(Errors::InvalidConfig(String::from(
+                "max-guesses must be at least 1",
+            )));
+        }
+
+        if settings.word_length == Some(0) {
+            return Err(Errors::InvalidConfig(String::from(
+                "word-length must be at least 1",
+            )));
+        }
+
+        Ok(settings)
+    }
+
+    /// Reads the settings from a config file. A config file that doesn't exist is treated the same
+    /// as an empty one, as the config file is optional
+    ///
+    /// Parameters
+    /// path:   The path of the config file
+    pub fn load(path: &Path) -> Result<PartialSettings, Errors> {
+        match fs::read_to_string(path) {
+            Ok(contents) => PartialSettings::parse(&contents),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(PartialSettings::default()),
+            Err(err) => Err(Errors::InvalidConfig(err.to_string())),
+        }
+    }
+}
+
+/// Returns the path of the config file, which is `wordle_cli/config.toml` in the XDG config
+/// directory. Returns None if neither XDG_CONFIG_HOME or HOME are set
+pub fn config_path() -> Option<PathBuf> {
+    let config_dir = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+
+    Some(config_dir.join("wordle_cli").join("config.toml"))
+}
+
+/// Returns the path of a file the game keeps its data in, such as stats, which is in `wordle_cli`
+/// in the XDG data directory. Returns None if neither XDG_DATA_HOME or HOME are set
+///
+/// Parameters
+/// file_name:  The name of the file
+pub fn data_path(file_name: &str) -> Option<PathBuf> {
+    let data_dir = match env::var_os("XDG_DATA_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?)
+            .join(".local")
+            .join("share"),
+    };
+
+    Some(data_dir.join("wordle_cli").join(file_name))
+}
+
+/// Struct to represent the settings the game is played with, after the config file and command
+/// line have been merged
+#[derive(Debug)]
+pub struct Settings {
+    pub word_list: Setting<Option<String>>,
+    pub definitions: Setting<Option<String>>,
+    pub theme: Setting<Palette>,
+    pub symbols: Setting<bool>,
+    pub animations: Setting<bool>,
+    pub stopwatch: Setting<bool>,
+    pub analysis: Setting<bool>,
+    pub compact: Setting<Option<bool>>,
+    pub hard_mode: Setting<bool>,
+    pub scoring: Setting<Scoring>,
+    pub max_guesses: Setting<usize>,
+    pub hints: Setting<usize>,
+    pub difficulty: Setting<Option<Difficulty>>,
+    pub word_length: Setting<usize>,
+}
+
+impl Settings {
+    /// Returns a Settings struct
+    ///
+    /// Parameters
+    /// config_file:    The settings read from the config file
+    /// command_line:   The settings given on the command line, which override the config file
+    pub fn resolve(config_file: PartialSettings, command_line: PartialSettings) -> Settings {
+        Settings {
+            word_list: Setting::resolve(
+                None,
+                config_file.word_list.map(Some),
+                command_line.word_list.map(Some),
+            ),
+            definitions: Setting::resolve(
+                None,
+                config_file.definitions.map(Some),
+                command_line.definitions.map(Some),
+            ),
+            theme: Setting::resolve(Palette::default(), config_file.theme, command_line.theme),
+            symbols: Setting::resolve(false, config_file.symbols, command_line.symbols),
+            animations: Setting::resolve(false, config_file.animations, command_line.animations),
+            stopwatch: Setting::resolve(false, config_file.stopwatch, command_line.stopwatch),
+            analysis: Setting::resolve(false, config_file.analysis, command_line.analysis),
+            compact: Setting::resolve(
+                None,
+                config_file.compact.map(Some),
+                command_line.compact.map(Some),
+            ),
+            hard_mode: Setting::resolve(false, config_file.hard_mode, command_line.hard_mode),
+            scoring: Setting::resolve(
+                Scoring::default(),
+                config_file.scoring,
+                command_line.scoring,
+            ),
+            max_guesses: Setting::resolve(
+                DEFAULT_MAX_GUESSES,
+                config_file.max_guesses,
+                command_line.max_guesses,
+            ),
+            hints: Setting::resolve(DEFAULT_HINTS, config_file.hints, command_line.hints),
+            difficulty: Setting::resolve(
+                None,
+                config_file.difficulty.map(Some),
+                command_line.difficulty.map(Some),
+            ),
+            word_length: Setting::resolve(
+                DEFAULT_WORD_LENGTH,
+                config_file.word_length,
+                command_line.word_length,
+            ),
+        }
+    }
+
+    /// Returns the options to play a game with these settings. Options that can't be set in the
+    /// config file are left at their defaults
+    pub fn game_options(&self) -> GameOptions {
+        GameOptions {
+            word_list: self.word_list.value.clone(),
+            definitions: self.definitions.value.clone(),
+            show_word: false,
+            practice: false,
+            answer: AnswerChoice::Random,
+            protocol: Protocol::default(),
+            theme: Theme::new(self.theme.value, self.symbols.value),
+            animations: self.animations.value,
+            stopwatch: self.stopwatch.value,
+            analysis: self.analysis.value,
+            time_limit: None,
+            guess_time_limit: None,
+            compact: self.compact.value,
+            hard_mode: self.hard_mode.value,
+            scoring: self.scoring.value,
+            max_guesses: self.max_guesses.value,
+            hints: self.hints.value,
+            difficulty: self.difficulty.value,
+            word_length: self.word_length.value,
+        }
+    }
+}
+
+impl fmt::Display for Settings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let word_list = match &self.word_list.value {
+            Some(path) => format!("{:?}", path),
+            None => String::from("built in list"),
+        };
+        let definitions = match &self.definitions.value {
+            Some(path) => format!("{:?}", path),
+            None => String::from("none"),
+        };
+        let compact = match self.compact.value {
+            Some(compact) => compact.to_string(),
+            None => String::from("auto"),
+        };
+        let difficulty = match self.difficulty.value.and_then(|d| d.to_possible_value()) {
+            Some(value) => value.get_name().to_string(),
+            None => String::from("any"),
+        };
+        let theme = match self.theme.value.to_possible_value() {
+            Some(value) => value.get_name().to_string(),
+            None => format!("{:?}", self.theme.value),
+        };
+
+        let rows = [
+            ("word-list", word_list, self.word_list.source),
+            ("definitions", definitions, self.definitions.source),
+            ("theme", theme, self.theme.source),
+            (
+                "symbols",
+                self.symbols.value.to_string(),
+                self.symbols.source,
+            ),
+            (
+                "animations",
+                self.animations.value.to_string(),
+                self.animations.source,
+            ),
+            (
+                "stopwatch",
+                self.stopwatch.value.to_string(),
+                self.stopwatch.source,
+            ),
+            (
+                "analysis",
+                self.analysis.value.to_string(),
+                self.analysis.source,
+            ),
+            ("compact", compact, self.compact.source),
+            (
+                "hard-mode",
+                self.hard_mode.value.to_string(),
+                self.hard_mode.source,
+            ),
+            (
+                "scoring",
+                self.scoring.value.name().to_string(),
+                self.scoring.source,
+            ),
+            (
+                "max-guesses",
+                self.max_guesses.value.to_string(),
+                self.max_guesses.source,
+            ),
+            ("hints", self.hints.value.to_string(), self.hints.source),
+            ("difficulty", difficulty, self.difficulty.source),
+            (
+                "word-length",
+                self.word_length.value.to_string(),
+                self.word_length.source,
+            ),
+        ];
+
+        for (name, value, source) in rows {
+            writeln!(f, "{:<12} = {:<20} ({})", name, value, source)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Checks if a config file with every option set is parsed correctly
+    fn parse_reads_every_option() {
+        let contents = r#"
+            word-list = "wordle_words.txt"
+            theme = "high-contrast"
+            symbols = true
+            animations = true
+            hard-mode = true
+            scoring = "mastermind"
+            max-guesses = 8
+            hints = 1
+            word-length = 6
+        "#;
+
+        let settings = PartialSettings::parse(contents).unwrap();
+
+        assert!(settings.word_list == Some(String::from("wordle_words.txt")));
+        assert!(settings.theme == Some(Palette::HighContrast));
+        assert!(settings.symbols == Some(true));
+        assert!(settings.animations == Some(true));
+        assert!(settings.hard_mode == Some(true));
+        assert!(settings.scoring == Some(Scoring::Mastermind));
+        assert!(settings.max_guesses == Some(8));
+        assert!(settings.hints == Some(1));
+        assert!(settings.word_length == Some(6));
+    }
+
+    #[test]
+    /// Checks if unknown options and impossible values are rejected rather than ignored
+    fn parse_rejects_invalid_config() {
+        assert!(PartialSettings::parse("colour = \"red\"").is_err());
+        assert!(PartialSettings::parse("max-guesses = 0").is_err());
+    }
+
+    #[test]
+    /// Checks if the command line overrides the config file, which overrides the defaults
+    fn resolve_uses_highest_priority_source() {
+        let config_file = PartialSettings {
+            theme: Some(Palette::Colorblind),
+            max_guesses: Some(8),
+            ..Default::default()
+        };
+        let command_line = PartialSettings {
+            max_guesses: Some(4),
+            ..Default::default()
+        };
+
+        let settings = Settings::resolve(config_file, command_line);
+
+        assert!(settings.max_guesses.value == 4);
+        assert!(settings.max_guesses.source == Source::CommandLine);
+        assert!(settings.theme.value == Palette::Colorblind);
+        assert!(settings.theme.source == Source::ConfigFile);
+        assert!(settings.word_length.value == DEFAULT_WORD_LENGTH);
+        assert!(settings.word_length.source == Source::Default);
+
+        // Hints are off unless something turns them on
+        assert!(settings.hints.value == 0);
+        assert!(settings.hints.source == Source::Default);
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::fs;
+
+use crate::Errors;
+
+/// Struct to represent a file of definitions for the words in a word list, read so that answers
+/// can be explained without going online
+///
+/// Each line of the file is a word, a colon, then the definition, such as
+/// `cigar: a roll of tobacco leaves for smoking`. Empty lines and lines starting with `#` are
+/// skipped
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Definitions {
+    entries: HashMap<String, String>,
+}
+
+impl Definitions {
+    /// Parses the definitions out of the contents of a definitions file
+    ///
+    /// Parameters
+    /// contents:   The contents of the definitions file
+    pub fn parse(contents: &str) -> Result<Definitions, Errors> {
+        let mut entries: HashMap<String, String> = HashMap::new();
+
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (word, definition) = match line.split_once(':') {
+                Some((word, definition)) if !word.trim().is_empty() => (word, definition),
+                _ => {
+                    return Err(Errors::InvalidDefinitions(format!(
+                        "line {}: expected a word, a colon, then the definition",
+                        index + 1
+                    )))
+                }
+            };
+
+            // Case folding the words so that they match the case folded answers
+            entries.insert(word.trim().to_lowercase(), definition.trim().to_string());
+        }
+
+        Ok(Definitions { entries })
+    }
+
+    /// Reads the definitions from a file
+    ///
+    /// Parameters
+    /// path:   The path of the definitions file
+    pub fn load(path: &str) -> Result<Definitions, Errors> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Definitions::parse(&contents),
+            Err(err) => Err(Errors::InvalidDefinitions(err.to_string())),
+        }
+    }
+
+    /// Returns the definition of a word, if the file has one
+    ///
+    /// Parameters
+    /// word:   The word to look up, in any case
+    pub fn get(&self, word: &str) -> Option<&str> {
+        self.entries
+            .get(&word.trim().to_lowercase())
+            .map(String::as_str)
+    }
+
+    /// Returns the number of words with a definition
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether there are no definitions
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Checks if definitions are read whatever case the word is written in, skipping comments
+    fn parse_reads_definitions() {
+        let contents = "# Definitions for the test list\n\
+                        \n\
+                        Cigar: a roll of tobacco leaves for smoking\n\
+                        osier:  a willow used in basketry: also its twigs\n";
+
+        let definitions = Definitions::parse(contents).unwrap();
+
+        assert!(definitions.len() == 2);
+        assert!(definitions.get("CIGAR") == Some("a roll of tobacco leaves for smoking"));
+        assert!(definitions.get("osier") == Some("a willow used in basketry: also its twigs"));
+        assert!(definitions.get("crane").is_none());
+    }
+
+    #[test]
+    /// Checks if lines without a word and a colon are rejected with their line number
+    fn parse_rejects_lines_without_a_word() {
+        let err = Definitions::parse("cigar: a roll of tobacco\njust some text").unwrap_err();
+        assert!(
+            matches!(err, Errors::InvalidDefinitions(message) if message.starts_with("line 2"))
+        );
+
+        assert!(Definitions::parse(": no word").is_err());
+    }
+}
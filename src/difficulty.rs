@@ -0,0 +1,115 @@
+use clap::ValueEnum;
+use serde::Deserialize;
+
+use crate::solver;
+
+/// Enum for how hard the answers drawn for a game are
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Difficulty {
+    /// The easiest third of the word list
+    Easy,
+    /// The middle third of the word list
+    Medium,
+    /// The hardest third of the word list
+    Hard,
+}
+
+/// Returns the words of a list from easiest to hardest. A word is rated by how far down the list
+/// it is, as lists such as the built in one put the most common words first, and by how many
+/// guesses the solver takes to find it, with both counting equally
+///
+/// Parameters
+/// words:  The words to rate, in the order of the list
+pub fn rank(words: &[String]) -> Vec<String> {
+    let guesses = solver::guess_counts(words);
+
+    let fewest = guesses.values().copied().min().unwrap_or(0);
+    let most = guesses.values().copied().max().unwrap_or(0);
+
+    let mut rated: Vec<(f64, usize, &String)> = words
+        .iter()
+        .enumerate()
+        .map(|(position, word)| {
+            let position_rating = position as f64 / words.len() as f64;
+            let solver_rating = match most - fewest {
+                0 => 0.0,
+                spread => {
+                    (guesses.get(word).copied().unwrap_or(most) - fewest) as f64 / spread as f64
+                }
+            };
+
+            (position_rating + solver_rating, position, word)
+        })
+        .collect();
+
+    // Words with the same rating stay in list order, so the ranking is the same every time
+    rated.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+    rated.into_iter().map(|(_, _, word)| word.clone()).collect()
+}
+
+/// Returns the words a game at a difficulty draws its answer from, which is a third of the list.
+/// Lists too short to split into thirds are used whole
+///
+/// Parameters
+/// words:      The words of the list, in the order of the list
+/// difficulty: The difficulty to draw answers for
+pub fn band(words: &[String], difficulty: Difficulty) -> Vec<String> {
+    if words.len() < 3 {
+        return words.to_vec();
+    }
+
+    let ranked = rank(words);
+    let third = ranked.len() / 3;
+
+    let range = match difficulty {
+        Difficulty::Easy => 0..third,
+        Difficulty::Medium => third..ranked.len() - third,
+        Difficulty::Hard => ranked.len() - third..ranked.len(),
+    };
+
+    ranked[range].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a word list with the most common words first
+    fn test_words() -> Vec<String> {
+        [
+            "which", "there", "their", "about", "would", "other", "fjord", "nymph", "quaff",
+        ]
+        .iter()
+        .map(|word| word.to_string())
+        .collect()
+    }
+
+    #[test]
+    /// Checks if the bands split the list into thirds with no word in two bands
+    fn bands_split_the_list() {
+        let words = test_words();
+
+        let easy = band(&words, Difficulty::Easy);
+        let medium = band(&words, Difficulty::Medium);
+        let hard = band(&words, Difficulty::Hard);
+
+        assert!(easy.len() == 3 && medium.len() == 3 && hard.len() == 3);
+        assert!(easy
+            .iter()
+            .all(|word| !medium.contains(word) && !hard.contains(word)));
+        assert!(medium.iter().all(|word| !hard.contains(word)));
+    }
+
+    #[test]
+    /// Checks if words further down the list are rated harder
+    fn rare_words_are_harder() {
+        let ranked = rank(&test_words());
+
+        let position = |word: &str| ranked.iter().position(|w| w == word).unwrap();
+
+        assert!(position("which") < position("quaff"));
+        assert!(band(&test_words()[..2], Difficulty::Hard).len() == 2);
+    }
+}
@@ -0,0 +1,268 @@
+use std::cmp::Ordering;
+use std::time::Duration;
+
+use crate::game::Game;
+use crate::hint::{Hint, HintKind};
+use crate::timer::format_duration;
+use crate::word::{BuildErrors, Word};
+use crate::GameOptions;
+
+/// Struct to represent one of the players in a duel
+pub struct Duelist {
+    name: String,
+    game: Game,
+    time: Duration,
+}
+
+impl Duelist {
+    /// Returns the name of the player
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the player's own game
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Returns the total time the player has spent on their turns
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+}
+
+/// Enum for how a duel ended
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// The player at the index won
+    Winner(usize),
+    /// Neither player solved the word, or both solved it in the same number of guesses and time
+    Draw,
+}
+
+/// Struct to represent two players taking turns to guess the same word, each on their own board
+pub struct Duel {
+    players: [Duelist; 2],
+    turn: usize,
+}
+
+impl Duel {
+    /// Returns a Duel struct
+    ///
+    /// Parameters
+    /// names:          The names of the two players, in the order they take turns
+    /// word_to_guess:  The word both players are trying to guess
+    /// words:          The wordlist the players are guessing from
+    /// options:        The options both players' games are played with
+    pub fn new(
+        names: [String; 2],
+        word_to_guess: String,
+        words: Vec<String>,
+        options: &GameOptions,
+    ) -> Duel {
+        let players = names.map(|name| Duelist {
+            name,
+            game: Game::new(
+                word_to_guess.clone(),
+                words.clone(),
+                options.max_guesses,
+                options.hard_mode,
+            )
+            .with_hints(options.hints)
+            .with_scoring(options.scoring),
+            time: Duration::ZERO,
+        });
+
+        Duel { players, turn: 0 }
+    }
+
+    /// Makes a guess for the player whose turn it is, returning the scored guess if it was valid.
+    /// The turn only passes to the other player after a valid guess, and never passes to a player
+    /// whose game is already over
+    ///
+    /// Parameters
+    /// guess:      The guess the player made
+    /// elapsed:    How long the player took over their turn
+    pub fn guess(&mut self, guess: &str, elapsed: Duration) -> Result<&Word, BuildErrors> {
+        let current = self.turn;
+        let player = &mut self.players[current];
+
+        player.game.guess(guess)?;
+        player.time += elapsed;
+
+        let other = 1 - current;
+        if !self.players[other].game.is_over() {
+            self.turn = other;
+        }
+
+        Ok(self.players[current].game.guesses().last().unwrap())
+    }
+
+    /// Gives the player whose turn it is a hint, returning it if there was one to give. Asking for
+    /// a hint doesn't pass the turn
+    ///
+    /// Parameters
+    /// kind:   The kind of hint the player asked for
+    pub fn hint(&mut self, kind: HintKind) -> Option<&Hint> {
+        self.players[self.turn].game.hint(kind)
+    }
+
+    /// Returns both players, in the order they take turns
+    pub fn players(&self) -> &[Duelist; 2] {
+        &self.players
+    }
+
+    /// Returns the index of the player whose turn it is
+    pub fn turn(&self) -> usize {
+        self.turn
+    }
+
+    /// Returns the player whose turn it is
+    pub fn current(&self) -> &Duelist {
+        &self.players[self.turn]
+    }
+
+    /// Returns the word both players are trying to guess
+    pub fn word_to_guess(&self) -> &str {
+        self.players[0].game.word_to_guess()
+    }
+
+    /// Returns whether both players' games are over
+    pub fn is_over(&self) -> bool {
+        self.players.iter().all(|player| player.game.is_over())
+    }
+
+    /// Returns how the duel ended. Solving the word beats not solving it, then fewer guesses win,
+    /// and ties are broken by the time each player spent on their turns
+    pub fn outcome(&self) -> Outcome {
+        let [first, second] = &self.players;
+
+        let ordering = match (first.game.is_won(), second.game.is_won()) {
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => Ordering::Equal,
+            (true, true) => first
+                .game
+                .guesses()
+                .len()
+                .cmp(&second.game.guesses().len())
+                .then(first.time.cmp(&second.time)),
+        };
+
+        match ordering {
+            Ordering::Less => Outcome::Winner(0),
+            Ordering::Greater => Outcome::Winner(1),
+            Ordering::Equal => Outcome::Draw,
+        }
+    }
+
+    /// Returns a line saying who won the duel and how
+    pub fn summary(&self) -> String {
+        match self.outcome() {
+            Outcome::Winner(i) => {
+                let winner = &self.players[i];
+
+                format!(
+                    "{} wins, solving it in {} guesses and {}",
+                    winner.name,
+                    winner.game.guesses().len(),
+                    format_duration(winner.time)
+                )
+            }
+            Outcome::Draw if self.players[0].game.is_won() => {
+                String::from("It's a draw, both players solved it in the same guesses and time")
+            }
+            Outcome::Draw => String::from("It's a draw, neither player solved it"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{PartialSettings, Settings};
+    use crate::scoring::Scoring;
+
+    /// Returns the options duels are played with when nothing is set
+    fn default_options() -> GameOptions {
+        Settings::resolve(PartialSettings::default(), PartialSettings::default()).game_options()
+    }
+
+    /// Returns a duel between alice and bob over guessing "cigar"
+    ///
+    /// Parameters
+    /// options:    The options the duel is played with
+    fn duel_with(options: &GameOptions) -> Duel {
+        let words = vec![
+            String::from("cigar"),
+            String::from("crane"),
+            String::from("moist"),
+        ];
+
+        Duel::new(
+            [String::from("alice"), String::from("bob")],
+            String::from("cigar"),
+            words,
+            options,
+        )
+    }
+
+    /// Returns a duel between alice and bob over guessing "cigar", with the default options
+    fn test_duel() -> Duel {
+        duel_with(&default_options())
+    }
+
+    #[test]
+    /// Checks if turns only pass after valid guesses, and are skipped for finished players
+    fn turns_alternate_between_players() {
+        let mut duel = test_duel();
+
+        assert!(duel.guess("zzzzz", Duration::ZERO).is_err());
+        assert!(duel.turn() == 0);
+
+        duel.guess("cigar", Duration::ZERO).unwrap();
+        assert!(duel.turn() == 1);
+
+        duel.guess("crane", Duration::ZERO).unwrap();
+        assert!(duel.turn() == 1);
+        assert!(duel.players()[1].game().guesses().len() == 1);
+    }
+
+    #[test]
+    /// Checks if fewer guesses win, with ties broken by time
+    fn outcome_uses_guesses_then_time() {
+        let mut duel = test_duel();
+        duel.guess("crane", Duration::from_secs(1)).unwrap();
+        duel.guess("cigar", Duration::from_secs(9)).unwrap();
+        duel.guess("cigar", Duration::from_secs(1)).unwrap();
+        assert!(duel.outcome() == Outcome::Winner(1));
+
+        let mut duel = test_duel();
+        duel.guess("cigar", Duration::from_secs(5)).unwrap();
+        duel.guess("cigar", Duration::from_secs(3)).unwrap();
+        assert!(duel.is_over());
+        assert!(duel.outcome() == Outcome::Winner(1));
+    }
+
+    #[test]
+    /// Checks if both players' games use the scoring and hints from the options
+    fn options_apply_to_both_players() {
+        let options = GameOptions {
+            scoring: Scoring::Mastermind,
+            hints: 1,
+            ..default_options()
+        };
+        let mut duel = duel_with(&options);
+
+        for player in duel.players() {
+            assert!(player.game().scoring() == Scoring::Mastermind);
+            assert!(player.game().max_hints() == 1);
+        }
+
+        // A hint is only used up by the player who asked for it, and keeps their turn
+        assert!(duel.hint(HintKind::Reveal).is_some());
+        assert!(duel.turn() == 0);
+        assert!(duel.players()[0].game().hints_left() == 0);
+        assert!(duel.players()[1].game().hints_left() == 1);
+    }
+}
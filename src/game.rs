@@ -0,0 +1,365 @@
+use rand::seq::SliceRandom;
+
+use crate::hint::{Hint, HintKind, ELIMINATED_PER_HINT};
+use crate::letter::LetterState;
+use crate::scoring::Scoring;
+use crate::word::BuildErrors;
+use crate::word::Word;
+
+/// Struct to represent the state of a game of wordle
+pub struct Game {
+    word_to_guess: String,
+    words: Vec<String>,
+    guesses: Vec<Word>,
+    max_guesses: usize,
+    hard_mode: bool,
+    timed_out: bool,
+    hints: Vec<Hint>,
+    max_hints: usize,
+    scoring: Scoring,
+}
+
+impl Game {
+    /// Returns a Game struct
+    ///
+    /// Parameters
+    /// word_to_guess:  The word the player is trying to guess
+    /// words:          The wordlist the player is guessing from
+    /// max_guesses:    The number of guesses the player gets
+    /// hard_mode:      Whether guesses have to use every hint revealed so far
+    pub fn new(
+        word_to_guess: String,
+        words: Vec<String>,
+        max_guesses: usize,
+        hard_mode: bool,
+    ) -> Game {
+        Game {
+            word_to_guess: word_to_guess.to_lowercase(),
+            words,
+            guesses: Vec::new(),
+            max_guesses,
+            hard_mode,
+            timed_out: false,
+            hints: Vec::new(),
+            max_hints: 0,
+            scoring: Scoring::default(),
+        }
+    }
+
+    /// Returns the game with a number of hints the player can ask for. Games have no hints unless
+    /// they are given some
+    ///
+    /// Parameters
+    /// max_hints:  The number of hints the player gets
+    pub fn with_hints(mut self, max_hints: usize) -> Game {
+        self.max_hints = max_hints;
+        self
+    }
+
+    /// Returns the game with guesses scored by a different set of rules. Games score every letter
+    /// unless they are told otherwise
+    ///
+    /// Parameters
+    /// scoring:    The rules guesses are scored by
+    pub fn with_scoring(mut self, scoring: Scoring) -> Game {
+        self.scoring = scoring;
+        self
+    }
+
+    /// Makes a guess, returning the scored guess if it was valid
+    ///
+    /// Parameters
+    /// guess:  The guess the player made
+    pub fn guess(&mut self, guess: &str) -> Result<&Word, BuildErrors> {
+        // Case folding the guess so that guesses like "CRANE" are treated the same as "crane"
+        let guess = guess.trim().to_lowercase();
+
+        // In hard mode the guess also has to use every hint the previous guesses revealed
+        if self.hard_mode && !self.guesses.iter().all(|word| word.hints_used_by(&guess)) {
+            return Err(BuildErrors::IgnoredHint);
+        }
+
+        let word = Word::new(guess, &self.word_to_guess, &self.words)?.with_scoring(self.scoring);
+
+        self.guesses.push(word);
+
+        Ok(self.guesses.last().unwrap())
+    }
+
+    /// Gives the player a hint, returning it if there was one to give. No hint is given once the
+    /// hints are used up, the game is over or there is nothing left for that kind of hint to show
+    ///
+    /// Parameters
+    /// kind:   The kind of hint the player asked for
+    pub fn hint(&mut self, kind: HintKind) -> Option<&Hint> {
+        if self.hints_left() == 0 || self.is_over() {
+            return None;
+        }
+
+        let mut rng = rand::thread_rng();
+
+        let hint = match kind {
+            HintKind::Reveal => {
+                // Only positions the player hasn't already found, by a guess or an earlier hint
+                let hidden: Vec<usize> = (0..self.word_length())
+                    .filter(|&index| !self.is_position_known(index))
+                    .collect();
+
+                let index = *hidden.choose(&mut rng)?;
+                let letter = self.word_to_guess.chars().nth(index)?;
+
+                Hint::Reveal { index, letter }
+            }
+            HintKind::Eliminate => {
+                // Only letters the player couldn't already have ruled out
+                let unknown: Vec<char> = ('a'..='z')
+                    .filter(|&letter| {
+                        !self.word_to_guess.contains(letter) && !self.is_letter_known(letter)
+                    })
+                    .collect();
+
+                if unknown.is_empty() {
+                    return None;
+                }
+
+                let mut letters: Vec<char> = unknown
+                    .choose_multiple(&mut rng, ELIMINATED_PER_HINT)
+                    .copied()
+                    .collect();
+                letters.sort_unstable();
+
+                Hint::Eliminate(letters)
+            }
+        };
+
+        self.hints.push(hint);
+
+        self.hints.last()
+    }
+
+    /// Returns whether the letter at a position has been found, by a guess or a hint
+    ///
+    /// Parameters
+    /// index:  The position in the word, counting from 0
+    fn is_position_known(&self, index: usize) -> bool {
+        // Only letter scoring can be trusted to say which letters are in the right place, so with
+        // other rules only hints find positions
+        let guessed = self.scoring == Scoring::Letters
+            && self.guesses.iter().any(|word| {
+                word.letters()
+                    .get(index)
+                    .is_some_and(|letter| letter.state() == LetterState::RightLetterRightPlace)
+            });
+
+        guessed
+            || self
+                .hints
+                .iter()
+                .any(|hint| matches!(hint, Hint::Reveal { index: i, .. } if *i == index))
+    }
+
+    /// Returns whether a letter has already been guessed or ruled out by a hint
+    ///
+    /// Parameters
+    /// letter: The letter to check
+    fn is_letter_known(&self, letter: char) -> bool {
+        let guessed = self.scoring == Scoring::Letters
+            && self
+                .guesses
+                .iter()
+                .any(|word| word.letters().iter().any(|l| *l.letter() == letter));
+
+        guessed
+            || self
+                .hints
+                .iter()
+                .any(|hint| matches!(hint, Hint::Eliminate(letters) if letters.contains(&letter)))
+    }
+
+    /// Returns the hints given so far, in the order they were asked for
+    pub fn hints(&self) -> &[Hint] {
+        &self.hints
+    }
+
+    /// Returns the number of hints the player gets
+    pub fn max_hints(&self) -> usize {
+        self.max_hints
+    }
+
+    /// Returns the number of hints the player has left
+    pub fn hints_left(&self) -> usize {
+        self.max_hints.saturating_sub(self.hints.len())
+    }
+
+    /// Returns the rules guesses are scored by
+    pub fn scoring(&self) -> Scoring {
+        self.scoring
+    }
+
+    /// Returns the word the player is trying to guess
+    pub fn word_to_guess(&self) -> &str {
+        &self.word_to_guess
+    }
+
+    /// Returns the wordlist the player is guessing from
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Returns the guesses the player has made so far
+    pub fn guesses(&self) -> &[Word] {
+        &self.guesses
+    }
+
+    /// Returns the number of guesses the player gets
+    pub fn max_guesses(&self) -> usize {
+        self.max_guesses
+    }
+
+    /// Returns the number of guesses the player has left
+    pub fn remaining_guesses(&self) -> usize {
+        self.max_guesses.saturating_sub(self.guesses.len())
+    }
+
+    /// Returns the length of the word to guess
+    pub fn word_length(&self) -> usize {
+        self.word_to_guess.chars().count()
+    }
+
+    /// Returns whether the player has guessed the word
+    pub fn is_won(&self) -> bool {
+        self.guesses
+            .last()
+            .is_some_and(|word| word.to_string() == self.word_to_guess)
+    }
+
+    /// Ends the game because the player ran out of time. Games that are already over are left as
+    /// they are
+    pub fn time_out(&mut self) {
+        if !self.is_over() {
+            self.timed_out = true;
+        }
+    }
+
+    /// Returns whether the game ended because the player ran out of time
+    pub fn is_timed_out(&self) -> bool {
+        self.timed_out
+    }
+
+    /// Returns whether the game is over, either because the player guessed the word or because
+    /// they ran out of guesses or time
+    pub fn is_over(&self) -> bool {
+        self.is_won() || self.remaining_guesses() == 0 || self.timed_out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a game of guessing "cigar" from a small wordlist
+    fn test_game(max_guesses: usize, hard_mode: bool) -> Game {
+        let words = vec![
+            String::from("cigar"),
+            String::from("crane"),
+            String::from("moist"),
+            String::from("clear"),
+        ];
+
+        Game::new(String::from("cigar"), words, max_guesses, hard_mode)
+    }
+
+    #[test]
+    /// Checks if the game is won and over once the word is guessed
+    fn guessing_the_word_wins() {
+        let mut game = test_game(6, false);
+
+        assert!(game.guess("crane").is_ok());
+        assert!(!game.is_over());
+        assert!(game.guess("CIGAR").is_ok());
+        assert!(game.is_won());
+        assert!(game.is_over());
+    }
+
+    #[test]
+    /// Checks if the game is over and not won once the player runs out of guesses
+    fn running_out_of_guesses_loses() {
+        let mut game = test_game(2, false);
+
+        game.guess("crane").unwrap();
+        game.guess("moist").unwrap();
+
+        assert!(!game.is_won());
+        assert!(game.is_over());
+    }
+
+    #[test]
+    /// Checks if hard mode rejects guesses that ignore revealed hints, without using up a guess
+    fn hard_mode_rejects_ignored_hints() {
+        let mut game = test_game(6, true);
+
+        game.guess("crane").unwrap();
+
+        assert!(matches!(game.guess("moist"), Err(BuildErrors::IgnoredHint)));
+        assert!(game.remaining_guesses() == 5);
+        assert!(game.guess("clear").is_ok());
+    }
+
+    #[test]
+    /// Checks if hard mode with Mastermind scoring only takes guesses that would have given every
+    /// guess so far the same pegs
+    fn mastermind_hard_mode_matches_pegs() {
+        let mut game = test_game(6, true).with_scoring(Scoring::Mastermind);
+
+        // "crane" gets 1 in place and 2 elsewhere. "clear" uses every letter the letter scoring
+        // would have shown, but would have given "crane" 1 in place and 3 elsewhere
+        game.guess("crane").unwrap();
+
+        assert!(game.guesses()[0].pegs().in_place == 1);
+        assert!(matches!(game.guess("clear"), Err(BuildErrors::IgnoredHint)));
+        assert!(game.guess("cigar").is_ok());
+    }
+
+    #[test]
+    /// Checks if running out of time ends the game as a loss, but not once the game is won
+    fn timing_out_loses() {
+        let mut game = test_game(6, false);
+        game.time_out();
+        assert!(game.is_over());
+        assert!(!game.is_won());
+
+        let mut game = test_game(6, false);
+        game.guess("cigar").unwrap();
+        game.time_out();
+        assert!(!game.is_timed_out());
+    }
+
+    #[test]
+    /// Checks if hints only show what the player doesn't know yet, and run out
+    fn hints_reveal_unknown_letters() {
+        let mut game = test_game(6, false).with_hints(2);
+
+        // "clear" finds the c in the first position
+        game.guess("clear").unwrap();
+
+        match game.hint(HintKind::Reveal).cloned() {
+            Some(Hint::Reveal { index, letter }) => {
+                assert!(index != 0);
+                assert!(game.word_to_guess().chars().nth(index) == Some(letter));
+            }
+            _ => panic!("expected a letter to be revealed"),
+        }
+
+        match game.hint(HintKind::Eliminate) {
+            Some(Hint::Eliminate(letters)) => {
+                assert!(letters.len() == ELIMINATED_PER_HINT);
+                assert!(letters.iter().all(|letter| !"cigarle".contains(*letter)));
+            }
+            _ => panic!("expected letters to be ruled out"),
+        }
+
+        assert!(game.hints_left() == 0);
+        assert!(game.hint(HintKind::Reveal).is_none());
+        assert!(game.hints().len() == 2);
+    }
+}
@@ -0,0 +1,124 @@
+use std::fmt;
+
+/// The number of letters ruled out by each hint that eliminates letters
+pub const ELIMINATED_PER_HINT: usize = 3;
+
+/// Enum for the kinds of hint the player can ask for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HintKind {
+    /// Reveal the letter at a position that hasn't been found yet
+    Reveal,
+    /// Rule out a handful of letters that aren't in the word
+    Eliminate,
+}
+
+impl HintKind {
+    /// Returns the kind of hint a key asks for, `?` to reveal a letter and `!` to rule out
+    /// letters, or None if the key isn't a hint key
+    ///
+    /// Parameters
+    /// key:    The key that was pressed
+    pub fn from_key(key: char) -> Option<HintKind> {
+        match key {
+            '?' => Some(HintKind::Reveal),
+            '!' => Some(HintKind::Eliminate),
+            _ => None,
+        }
+    }
+}
+
+/// Enum for the help a hint gave the player
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Hint {
+    /// The letter at a position in the word, counting from 0
+    Reveal { index: usize, letter: char },
+    /// Letters that aren't in the word
+    Eliminate(Vec<char>),
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Hint::Reveal { index, letter } => {
+                write!(f, "Letter {} is {}", index + 1, letter.to_uppercase())
+            }
+            Hint::Eliminate(letters) => {
+                let letters: Vec<String> = letters
+                    .iter()
+                    .map(|letter| letter.to_uppercase().to_string())
+                    .collect();
+
+                write!(f, "The word has no {}", letters.join(", "))
+            }
+        }
+    }
+}
+
+/// Returns the message to show when a hint was asked for but none could be given
+///
+/// Parameters
+/// hints_left: The number of hints the player had left
+pub fn no_hint_message(hints_left: usize) -> &'static str {
+    match hints_left {
+        0 => "No hints left",
+        _ => "There is nothing left for that hint to show",
+    }
+}
+
+/// Returns the line telling the player how to ask for hints
+///
+/// Parameters
+/// hints_left: The number of hints the player has left
+pub fn help_line(hints_left: usize) -> String {
+    format!(
+        "? reveals a letter, ! rules out letters ({} left)",
+        hints_left
+    )
+}
+
+/// Returns a line summing up every hint given so far, with revealed letters in their places and
+/// the letters that were ruled out after them, such as `_ _ R _ _   not B, K, V`
+///
+/// Parameters
+/// hints:          The hints given so far
+/// word_length:    The length of the word to guess
+pub fn hint_line(hints: &[Hint], word_length: usize) -> String {
+    let mut places = vec![String::from("_"); word_length];
+    let mut eliminated: Vec<String> = Vec::new();
+
+    for hint in hints {
+        match hint {
+            Hint::Reveal { index, letter } => places[*index] = letter.to_uppercase().to_string(),
+            Hint::Eliminate(letters) => eliminated.extend(
+                letters
+                    .iter()
+                    .map(|letter| letter.to_uppercase().to_string()),
+            ),
+        }
+    }
+
+    match eliminated.is_empty() {
+        true => places.join(" "),
+        false => format!("{}   not {}", places.join(" "), eliminated.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Checks if the hint line puts revealed letters in place and lists ruled out letters
+    fn hint_line_sums_up_hints() {
+        let hints = vec![
+            Hint::Reveal {
+                index: 2,
+                letter: 'g',
+            },
+            Hint::Eliminate(vec!['b', 'k']),
+        ];
+
+        assert!(hint_line(&hints, 5) == "_ _ G _ _   not B, K");
+        assert!(hint_line(&hints[..1], 5) == "_ _ G _ _");
+    }
+}
@@ -0,0 +1,429 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, stdin, stdout, ErrorKind, IsTerminal, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+use crate::game::Game;
+use crate::render::{state_from_name, state_name, Renderer};
+use crate::scoring::Scoring;
+use crate::timer::{format_duration, Timer};
+use crate::word::Word;
+use crate::{days_since_epoch, Errors};
+
+/// The name of the file the history is kept in, in the data directory
+pub const HISTORY_FILE: &str = "history.jsonl";
+
+/// Enum for the modes a game in the history was played in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Mode {
+    /// A single game with a random word
+    Random,
+    /// The word of the day
+    Daily,
+    /// One of the puzzles in a speedrun
+    Speedrun,
+    /// One of the rounds in endless mode
+    Endless,
+    /// A word someone chose, from a challenge code
+    Custom,
+    /// A practice game, with the words still possible shown after each guess
+    Practice,
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Mode::Random => write!(f, "random"),
+            Mode::Daily => write!(f, "daily"),
+            Mode::Speedrun => write!(f, "speedrun"),
+            Mode::Endless => write!(f, "endless"),
+            Mode::Custom => write!(f, "custom"),
+            Mode::Practice => write!(f, "practice"),
+        }
+    }
+}
+
+/// Struct to represent a letter that Fibble scoring lied about in a guess kept in the history
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lie {
+    /// The position of the letter in the guess
+    pub index: usize,
+    /// The state the letter was shown with, named the same as in JSON output
+    pub shown: String,
+}
+
+/// Struct to represent a finished game kept in the history. Only the guesses are kept, as their
+/// colours can be worked out again from the answer, along with any lies the scoring told as they
+/// were chosen at random
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// The day the game was played on, such as 2024-03-09
+    pub date: String,
+    /// The word that had to be guessed
+    pub answer: String,
+    /// Every guess made, in order
+    pub guesses: Vec<String>,
+    /// The number of guesses the player had
+    pub max_guesses: usize,
+    /// The mode the game was played in
+    pub mode: Mode,
+    /// How long the game took, in milliseconds
+    pub duration_ms: u64,
+    /// Whether the player guessed the word
+    pub won: bool,
+    /// The number of hints the player asked for. Games kept before hints were added used none
+    #[serde(default)]
+    pub hints: usize,
+    /// The rules the guesses were scored by. Games kept before scoring rules were added were
+    /// scored letter by letter
+    #[serde(default)]
+    pub scoring: Scoring,
+    /// The letter each guess lied about, if it lied. Only kept for games scored by rules that lie
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lies: Vec<Option<Lie>>,
+}
+
+impl HistoryEntry {
+    /// Returns a HistoryEntry struct for a game finished today
+    ///
+    /// Parameters
+    /// game:   The finished game
+    /// timer:  The clock the game was played against
+    /// mode:   The mode the game was played in
+    pub fn new(game: &Game, timer: &Timer, mode: Mode) -> HistoryEntry {
+        HistoryEntry {
+            date: date_from_days(days_since_epoch()),
+            answer: game.word_to_guess().to_string(),
+            guesses: game.guesses().iter().map(Word::to_string).collect(),
+            max_guesses: game.max_guesses(),
+            mode,
+            duration_ms: timer.elapsed().as_millis() as u64,
+            won: game.is_won(),
+            hints: game.hints().len(),
+            scoring: game.scoring(),
+            lies: lies(game),
+        }
+    }
+
+    /// Returns the guesses scored against the answer again, using the same scoring as the game and
+    /// telling the same lies it told. Every stored guess was valid when it was made, so each guess
+    /// is only checked against itself rather than against a word list that may have changed since
+    pub fn rescore(&self) -> Vec<Word> {
+        self.guesses
+            .iter()
+            .enumerate()
+            .filter_map(|(i, guess)| {
+                let word =
+                    Word::new(guess.clone(), &self.answer, std::slice::from_ref(guess)).ok()?;
+
+                // The lie is told again before the scoring is applied, so no new lie is made up
+                let word = match self.lies.get(i).and_then(Option::as_ref) {
+                    Some(lie) => word.with_lie(lie.index, state_from_name(&lie.shown)?),
+                    None => word,
+                };
+
+                Some(word.with_scoring(self.scoring))
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for HistoryEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let result = match self.won {
+            true => format!("{}/{}", self.guesses.len(), self.max_guesses),
+            false => format!("X/{}", self.max_guesses),
+        };
+
+        write!(
+            f,
+            "{}  {:<8}  {}  {:<5}  {}",
+            self.date,
+            self.mode,
+            self.answer,
+            result,
+            format_duration(Duration::from_millis(self.duration_ms))
+        )
+    }
+}
+
+/// Returns the letter each guess of a game lied about, or nothing if the game's scoring never lies
+///
+/// Parameters
+/// game:   The finished game
+fn lies(game: &Game) -> Vec<Option<Lie>> {
+    if game.scoring() != Scoring::Fibble {
+        return Vec::new();
+    }
+
+    game.guesses()
+        .iter()
+        .map(|word| {
+            word.lie().map(|index| Lie {
+                index,
+                shown: state_name(&word.letters()[index].state()).to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Adds a game to the end of a history file, creating the file if needed
+///
+/// Parameters
+/// path:   The path of the history file
+/// entry:  The game to add
+pub fn append_to(path: &Path, entry: &HistoryEntry) -> Result<(), Errors> {
+    let to_error = |err: io::Error| Errors::InvalidHistory(err.to_string());
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(to_error)?;
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(to_error)?;
+
+    // Each game is one line of JSON, so games can be added without reading the whole file
+    let line =
+        serde_json::to_string(entry).map_err(|err| Errors::InvalidHistory(err.to_string()))?;
+
+    writeln!(file, "{}", line).map_err(to_error)
+}
+
+/// Reads every game from a history file, oldest first. A file that doesn't exist is treated as no
+/// games played yet
+///
+/// Parameters
+/// path:   The path of the history file
+pub fn load_from(path: &Path) -> Result<Vec<HistoryEntry>, Errors> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(Errors::InvalidHistory(err.to_string())),
+    };
+
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .map_err(|err| Errors::InvalidHistory(format!("game {}: {}", i + 1, err)))
+        })
+        .collect()
+}
+
+/// Adds a game to the history. When there is nowhere to keep the history the game isn't kept
+///
+/// Parameters
+/// entry:  The game to add
+pub fn append(entry: &HistoryEntry) -> Result<(), Errors> {
+    match config::data_path(HISTORY_FILE) {
+        Some(path) => append_to(&path, entry),
+        None => Ok(()),
+    }
+}
+
+/// Returns every game in the history, oldest first
+pub fn load() -> Result<Vec<HistoryEntry>, Errors> {
+    match config::data_path(HISTORY_FILE) {
+        Some(path) => load_from(&path),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Replays a game from the history one guess at a time, drawing each guess with its original
+/// colours. When stdin is a terminal the next guess is shown after Enter is pressed, otherwise
+/// every guess is shown straight away
+///
+/// Parameters
+/// entry:      The game to replay
+/// renderer:   The renderer to draw the guesses with
+pub fn replay(entry: &HistoryEntry, renderer: &dyn Renderer) -> io::Result<()> {
+    let step = stdin().is_terminal();
+    let words = entry.rescore();
+
+    println!("{}", entry);
+
+    for (i, word) in words.iter().enumerate() {
+        for line in renderer.render_guess(word) {
+            println!("{}", line);
+        }
+
+        if step && i < words.len() - 1 {
+            print!("Press Enter for guess {} of {}", i + 2, words.len());
+            stdout().flush()?;
+            stdin().read_line(&mut String::new())?;
+        }
+    }
+
+    match entry.won {
+        true => println!("Solved in {} guesses", words.len()),
+        false => println!("The word was {}", entry.answer),
+    }
+
+    Ok(())
+}
+
+/// Returns a day counted from the unix epoch as a date, such as 2024-03-09
+///
+/// Parameters
+/// days:   The number of days since 1970-01-01
+pub fn date_from_days(days: u64) -> String {
+    // Converting days to a date in the proleptic Gregorian calendar, counting 400 year eras from
+    // 0000-03-01 so that leap days fall at the end of each year
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::letter::LetterState;
+    use crate::render::PlainRenderer;
+
+    /// Returns a won game of guessing "cigar" as kept in the history
+    fn test_entry() -> HistoryEntry {
+        HistoryEntry {
+            date: String::from("2024-03-09"),
+            answer: String::from("cigar"),
+            guesses: vec![String::from("crane"), String::from("cigar")],
+            max_guesses: 6,
+            mode: Mode::Daily,
+            duration_ms: 12_300,
+            won: true,
+            hints: 0,
+            scoring: Scoring::Letters,
+            lies: Vec::new(),
+        }
+    }
+
+    #[test]
+    /// Checks if days since the epoch are turned into the right dates, including leap days
+    fn date_from_days_handles_leap_years() {
+        assert!(date_from_days(0) == "1970-01-01");
+        assert!(date_from_days(19_782) == "2024-02-29");
+        assert!(date_from_days(19_783) == "2024-03-01");
+    }
+
+    #[test]
+    /// Checks if stored guesses are given the same colours they had when they were played
+    fn rescore_matches_original_colours() {
+        let words = test_entry().rescore();
+
+        assert!(words.len() == 2);
+        assert!(words[0].letters()[0].state() == LetterState::RightLetterRightPlace);
+        assert!(words[0].letters()[1].state() == LetterState::RightLetterWrongPlace);
+        assert!(words[0].letters()[4].state() == LetterState::WrongLetterWrongPlace);
+
+        // Games played with every other scoring rule are shown the way they were played, including
+        // the lies Fibble told
+        for scoring in [Scoring::Letters, Scoring::Mastermind, Scoring::Fibble] {
+            let words = vec![
+                String::from("cigar"),
+                String::from("crane"),
+                String::from("moist"),
+            ];
+            let mut game = Game::new(String::from("cigar"), words, 6, false).with_scoring(scoring);
+            game.guess("crane").unwrap();
+            game.guess("moist").unwrap();
+
+            let entry = HistoryEntry::new(&game, &Timer::new(None, None), Mode::Random);
+            let replayed: Vec<String> = entry.rescore().iter().map(Word::plain).collect();
+            let played: Vec<String> = game.guesses().iter().map(Word::plain).collect();
+
+            assert!(replayed == played);
+        }
+    }
+
+    #[test]
+    /// Checks if a game scored like Mastermind is replayed with its pegs, without colouring the
+    /// letters one by one
+    fn replay_keeps_mastermind_scoring() {
+        let entry = HistoryEntry {
+            scoring: Scoring::Mastermind,
+            ..test_entry()
+        };
+        let words = entry.rescore();
+
+        assert!(words[0].scoring() == Scoring::Mastermind);
+        assert!(PlainRenderer.render_guess(&words[0]) == vec!["CRANE  1 in place, 2 elsewhere"]);
+    }
+
+    #[test]
+    /// Checks if a Fibble game is replayed with the lies the player was shown, not the truth
+    fn replay_keeps_fibble_lies() {
+        let entry = HistoryEntry {
+            scoring: Scoring::Fibble,
+            lies: vec![
+                Some(Lie {
+                    index: 4,
+                    shown: String::from("correct"),
+                }),
+                None,
+            ],
+            ..test_entry()
+        };
+        let words = entry.rescore();
+
+        // The e of crane was shown as in the right place, and the winning guess didn't lie
+        assert!(words[0].lie() == Some(4));
+        assert!(words[0].letters()[4].state() == LetterState::RightLetterRightPlace);
+        assert!(words[0].revealed().letters()[4].state() == LetterState::WrongLetterWrongPlace);
+        assert!(words[1].lie().is_none());
+    }
+
+    #[test]
+    /// Checks if games kept before scoring rules were added are still read, as letter scored
+    fn old_entries_are_letter_scored() {
+        let line = r#"{"date":"2024-03-09","answer":"cigar","guesses":["cigar"],"max_guesses":6,"mode":"daily","duration_ms":5000,"won":true}"#;
+        let entry: HistoryEntry = serde_json::from_str(line).unwrap();
+
+        assert!(entry.scoring == Scoring::Letters);
+    }
+
+    #[test]
+    /// Checks if games are added to the end of the history file and read back in order
+    fn history_round_trips_through_file() {
+        let path =
+            std::env::temp_dir().join(format!("wordle_cli_history_{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let first = test_entry();
+        let second = HistoryEntry {
+            mode: Mode::Endless,
+            won: false,
+            ..test_entry()
+        };
+
+        append_to(&path, &first).unwrap();
+        append_to(&path, &second).unwrap();
+
+        let history = load_from(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(history == vec![first, second]);
+    }
+}
@@ -1,6 +1,7 @@
-use colored::Colorize;
+use crate::theme::Theme;
 
 /// Struct to represent what state the letter is (Right or wrong essentially)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum LetterState {
     WrongLetterWrongPlace,
     RightLetterWrongPlace,
@@ -36,11 +37,48 @@ impl Letter {
         &self.letter
     }
 
+    /// Returns the letter in plain text notation, where a right letter in the right place is
+    /// wrapped in square brackets, a right letter in the wrong place is wrapped in parentheses, and
+    /// a wrong letter is left lowercase
+    pub fn plain(&self) -> String {
+        let letter = self.letter.to_uppercase();
+
+        match self.letter_state {
+            LetterState::RightLetterRightPlace => format!("[{}]", letter),
+            LetterState::RightLetterWrongPlace => format!("({})", letter),
+            LetterState::WrongLetterWrongPlace => self.letter.to_lowercase().to_string(),
+        }
+    }
+
+    /// Returns the state of the letter
+    pub fn state(&self) -> LetterState {
+        self.letter_state
+    }
+
+    /// Returns the letter as a single coloured cell, used by the compact layout
+    ///
+    /// Parameters
+    /// theme:    The theme to style the cell with
+    pub fn get_compact(&self, theme: &Theme) -> String {
+        let cell = if theme.symbols() {
+            format!(
+                " {}{}",
+                self.letter.to_uppercase(),
+                Theme::symbol(&self.letter_state)
+            )
+        } else {
+            format!(" {} ", self.letter.to_uppercase())
+        };
+
+        theme.paint_background(&cell, &self.letter_state)
+    }
+
     /// Returns a string containg the corresponding row of the letter
     ///
     /// Parameters
     /// index:    The row to get(Between 0-2)
-    pub fn get_row(&self, index: usize) -> Result<String, &'static str> {
+    /// theme:    The theme to style the row with
+    pub fn get_row(&self, index: usize, theme: &Theme) -> Result<String, &'static str> {
         // Returning an error if the index is greater the the num of rows to prevent a panic
         if index > self.rows.len() {
             return Err("That row does not exist!");
@@ -48,20 +86,25 @@ impl Letter {
 
         let mut row = self.rows[index].clone();
 
-        // Colouring the box depending on the state of the letter
-        match self.letter_state {
-            LetterState::RightLetterRightPlace => row = row.bright_green().to_string(),
-            LetterState::RightLetterWrongPlace => row = row.bright_yellow().to_string(),
-            LetterState::WrongLetterWrongPlace => (),
+        // Marking the state of the letter with a symbol in the middle row of the box, so that the
+        // state can be read without relying on colour
+        if theme.symbols() && index == 1 {
+            row = format!(
+                "│ {}{}│",
+                self.letter.to_uppercase(),
+                Theme::symbol(&self.letter_state)
+            );
         }
 
-        Ok(row)
+        // Colouring the box depending on the state of the letter
+        Ok(theme.paint(&row, &self.letter_state))
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::theme::Palette;
 
     #[test]
     /// Test for checking if the Letter struct is generated correctly
@@ -76,7 +119,7 @@ mod test {
     fn get_row_returns_right_row_one() {
         let letter_struct = Letter::new('w', LetterState::WrongLetterWrongPlace);
 
-        let row_one = letter_struct.get_row(0);
+        let row_one = letter_struct.get_row(0, &Theme::default());
 
         assert!(row_one.unwrap() == String::from("╭───╮"))
     }
@@ -86,7 +129,7 @@ mod test {
     fn get_row_returns_right_row_two() {
         let letter_struct = Letter::new('w', LetterState::WrongLetterWrongPlace);
 
-        let row_one = letter_struct.get_row(1);
+        let row_one = letter_struct.get_row(1, &Theme::default());
 
         assert!(row_one.unwrap() == String::from("│ W │"))
     }
@@ -96,9 +139,41 @@ mod test {
     fn get_row_returns_right_row_three() {
         let letter_struct = Letter::new('w', LetterState::WrongLetterWrongPlace);
 
-        let row_one = letter_struct.get_row(2);
+        let row_one = letter_struct.get_row(2, &Theme::default());
 
         assert!(row_one.unwrap() == String::from("╰───╯"))
     }
 
+    #[test]
+    /// Test to see if the plain() function uses the right notation for each letter state
+    fn plain_uses_notation() {
+        let right = Letter::new('r', LetterState::RightLetterRightPlace);
+        let close = Letter::new('n', LetterState::RightLetterWrongPlace);
+        let wrong = Letter::new('a', LetterState::WrongLetterWrongPlace);
+
+        assert!(right.plain() == "[R]");
+        assert!(close.plain() == "(N)");
+        assert!(wrong.plain() == "a");
+    }
+
+    #[test]
+    /// Test to see if the get_row() function marks the letter with a symbol when the theme asks
+    /// for it
+    fn get_row_marks_symbol() {
+        let letter_struct = Letter::new('w', LetterState::WrongLetterWrongPlace);
+
+        let row_two = letter_struct.get_row(1, &Theme::new(Palette::Classic, true));
+
+        assert!(row_two.unwrap() == "│ W✗│")
+    }
+
+    #[test]
+    /// Test to see if the get_compact() function returns the letter as a single cell
+    fn get_compact_returns_cell() {
+        let letter_struct = Letter::new('w', LetterState::WrongLetterWrongPlace);
+
+        let cell = letter_struct.get_compact(&Theme::new(Palette::Classic, true));
+
+        assert!(cell.contains(" W✗"))
+    }
 }
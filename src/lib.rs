@@ -1,171 +1,783 @@
+pub mod analysis;
+pub mod animation;
+pub mod code;
+pub mod config;
+pub mod definitions;
+pub mod difficulty;
+pub mod duel;
+pub mod game;
+pub mod hint;
+pub mod history;
 pub mod letter;
+pub mod lint;
+pub mod multiplayer;
+pub mod protocol;
+pub mod render;
+pub mod scoring;
+pub mod served;
+pub mod solver;
+pub mod stats;
+pub mod theme;
+pub mod timer;
+pub mod tui;
 pub mod word;
 pub mod wordlist;
-
+pub mod wordstats;
+
+use definitions::Definitions;
+use difficulty::Difficulty;
+use duel::Duel;
+use game::Game;
+use hint::HintKind;
+use history::{HistoryEntry, Mode};
+use protocol::Protocol;
+use render::{grid_renderer, side_by_side, CompactRenderer, PlainRenderer, Renderer};
+use scoring::Scoring;
+use theme::Theme;
+use timer::{format_duration, Timer};
 use word::BuildErrors;
-use word::Word;
 
 use wordlist::DEFAULT_WORDS;
 
 use rand::Rng;
 
+use std::env;
 use std::fs::File;
-use std::io::stdout;
 use std::io::{self, BufRead, BufReader};
+use std::io::{stdout, IsTerminal};
 use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use crossterm::{cursor, execute, style::Print, terminal};
+use crossterm::{execute, style::Print, terminal};
 
 use colored::Colorize;
 
-const MAX_GUESSES: usize = 6;
-
 /// Enum for possible errors
 #[derive(Debug)]
 pub enum Errors {
     FailedToOpenFile,
     NoWordsInFile,
+    NoWordsOfLength(usize),
+    InvalidConfig(String),
+    FailedToWriteOutput,
+    Network(String),
+    InvalidStats(String),
+    InvalidHistory(String),
+    InvalidServed(String),
+    InvalidDefinitions(String),
+    InvalidCode,
+    WordNotInList(String),
+    Terminal(String),
+}
+
+/// Enum for the ways the word to guess can be chosen
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnswerChoice {
+    /// A random word from the word list
+    Random,
+    /// The word of the day, which is the same for everyone playing with the same word list
+    Daily,
+    /// A word someone chose, such as one decoded from a challenge code
+    Fixed(String),
+}
+
+/// Struct to represent the options a game is played with
+#[derive(Debug)]
+pub struct GameOptions {
+    /// The path to the word file to choose the random word from
+    pub word_list: Option<String>,
+    /// The path to the file of definitions to explain the answer with, if there is one
+    pub definitions: Option<String>,
+    /// Whether to print the word to guess
+    pub show_word: bool,
+    /// Whether to show the words that are still possible, for practice
+    pub practice: bool,
+    /// How the word to guess is chosen
+    pub answer: AnswerChoice,
+    /// How the game talks to whoever is playing it
+    pub protocol: Protocol,
+    /// The theme to draw the guesses with
+    pub theme: Theme,
+    /// Whether to animate guesses being revealed
+    pub animations: bool,
+    /// Whether to show a stopwatch while playing
+    pub stopwatch: bool,
+    /// Whether to show how much each guess narrowed down the answer once the game is over
+    pub analysis: bool,
+    /// How long the whole game can take, if it is limited
+    pub time_limit: Option<Duration>,
+    /// How long each guess can take, if it is limited
+    pub guess_time_limit: Option<Duration>,
+    /// Whether to draw each guess on one line, or None to decide based on the terminal height
+    pub compact: Option<bool>,
+    /// Whether guesses have to use every hint revealed so far
+    pub hard_mode: bool,
+    /// The rules guesses are scored by
+    pub scoring: Scoring,
+    /// The number of guesses the player gets
+    pub max_guesses: usize,
+    /// The number of hints the player can ask for during a game
+    pub hints: usize,
+    /// How hard the random answers are, or None to draw from the whole word list
+    pub difficulty: Option<Difficulty>,
+    /// The length of the words to guess
+    pub word_length: usize,
 }
 
 /// Plays the game
 ///
 /// Parameters
-/// wordfile:    The path to the word file to choose the random word from
-/// show_word:   Whether to print the word to guess
-pub fn play(wordfile: Option<String>, show_word: bool) -> Result<(), Errors> {
+/// options:    The options to play the game with
+pub fn play(options: GameOptions) -> Result<(), Errors> {
     // Getting the list of words to choose from out of the file supplied
-    let words_to_guess = match wordfile {
-        Some(file) => lines_from_file(file)?,
-        None => DEFAULT_WORDS.iter().map(|x| x.to_string()).collect(),
+    let (words_to_guess, word_to_guess) = words_and_answer(&options)?;
+
+    let mut game = Game::new(
+        word_to_guess,
+        words_to_guess,
+        options.max_guesses,
+        options.hard_mode,
+    )
+    .with_hints(options.hints)
+    .with_scoring(options.scoring);
+
+    // Bots get JSON without any colours, and the result is part of the JSON. Their games are left
+    // out of the stats and history, which are only for games people play
+    if options.protocol == Protocol::Json {
+        colored::control::set_override(false);
+
+        return protocol::play_json(&mut game, io::stdin().lock(), stdout(), options.show_word)
+            .map_err(|_| Errors::FailedToWriteOutput);
+    }
+
+    let definitions = load_definitions(&options)?;
+    let mut timer = Timer::new(options.time_limit, options.guess_time_limit);
+
+    play_human(&mut game, &options, &mut timer, definitions.as_ref(), None)?;
+
+    let mode = match options.answer {
+        _ if options.practice => Mode::Practice,
+        AnswerChoice::Random => Mode::Random,
+        AnswerChoice::Daily => Mode::Daily,
+        AnswerChoice::Fixed(_) => Mode::Custom,
     };
+    record_game(&game, &timer, mode)?;
 
-    // Choosing a random word
-    let word_to_guess = random_word(&words_to_guess);
+    Ok(())
+}
 
-    // Printing out the word for testing purposes
-    if show_word {
-        println!("{}", &word_to_guess.bold());
+/// Plays a speedrun, where several puzzles are played one after another against the clock
+///
+/// Parameters
+/// options:    The options to play each puzzle with
+/// puzzles:    The number of puzzles to play
+pub fn play_speedrun(options: GameOptions, puzzles: usize) -> Result<(), Errors> {
+    let words_to_guess = load_words(options.word_list.clone(), options.word_length)?;
+    let answers = answer_pool(&words_to_guess, options.difficulty);
+    let definitions = load_definitions(&options)?;
+
+    // The stopwatch is the point of a speedrun, so it is always shown
+    let options = GameOptions {
+        stopwatch: true,
+        ..options
+    };
+
+    let mut total = Duration::ZERO;
+    let mut solved = 0;
+
+    for puzzle in 1..=puzzles {
+        let mut game = Game::new(
+            fresh_word(&answers)?,
+            words_to_guess.clone(),
+            options.max_guesses,
+            options.hard_mode,
+        )
+        .with_hints(options.hints)
+        .with_scoring(options.scoring);
+        let mut timer =
+            Timer::new(options.time_limit, options.guess_time_limit).with_carried(total);
+
+        let title = format!("Puzzle {} of {}", puzzle, puzzles);
+
+        play_human(
+            &mut game,
+            &options,
+            &mut timer,
+            definitions.as_ref(),
+            Some(&title),
+        )?;
+        record_game(&game, &timer, Mode::Speedrun)?;
+
+        // Only the time spent playing counts, not the time between puzzles
+        total += timer.elapsed();
+
+        // Quitting a puzzle part way through ends the whole speedrun
+        if !game.is_over() {
+            println!("Speedrun abandoned after {}", format_duration(total));
+            return Ok(());
+        }
+
+        if game.is_won() {
+            solved += 1;
+        }
     }
 
-    // Instantiating a vec to store the players guesses
-    let mut guesses: Vec<Word> = Vec::new();
+    println!(
+        "Solved {} of {} puzzles in {}",
+        solved,
+        puzzles,
+        format_duration(total)
+    );
 
-    let mut game_over = false;
+    // Only speedruns where every puzzle was solved count as personal bests
+    if solved == puzzles && stats::update(|stats| stats.record_speedrun(puzzles, total))? {
+        println!("New personal best for a speedrun of {}!", puzzles);
+    }
 
-    while !game_over {
-        // Clearing the screen from the cursor down. We don't move the cursor here as if we do it
-        // here the program would wipe lines above the cli game being player
-        execute!(
-            stdout(),
-            terminal::Clear(terminal::ClearType::FromCursorDown)
+    Ok(())
+}
+
+/// Plays endless mode, where a new word is served after each one is solved until the player fails
+/// to solve one. The guesses of every round are summarized once the run is over
+///
+/// Parameters
+/// options:    The options to play each round with
+pub fn play_endless(options: GameOptions) -> Result<(), Errors> {
+    let words_to_guess = load_words(options.word_list.clone(), options.word_length)?;
+
+    // Words already served are left out so a word can't come up twice in one run
+    let mut answers = answer_pool(&words_to_guess, options.difficulty);
+    let definitions = load_definitions(&options)?;
+    let mut rounds: Vec<Game> = Vec::new();
+
+    while !answers.is_empty() {
+        let word_to_guess = fresh_word(&answers)?;
+        answers.retain(|answer| *answer != word_to_guess);
+
+        let mut game = Game::new(
+            word_to_guess,
+            words_to_guess.clone(),
+            options.max_guesses,
+            options.hard_mode,
         )
-        .unwrap(); // This is probably unsafe...
+        .with_hints(options.hints)
+        .with_scoring(options.scoring);
+        let mut timer = Timer::new(options.time_limit, options.guess_time_limit);
+
+        let title = format!("Round {}, current run {}", rounds.len() + 1, rounds.len());
+
+        play_human(
+            &mut game,
+            &options,
+            &mut timer,
+            definitions.as_ref(),
+            Some(&title),
+        )?;
+        record_game(&game, &timer, Mode::Endless)?;
+
+        let solved = game.is_won();
+        rounds.push(game);
+
+        if !solved {
+            break;
+        }
+    }
+
+    // Only the rounds that were solved count towards the run
+    let run = rounds.iter().filter(|game| game.is_won()).count();
+
+    let renderer: Box<dyn Renderer> = match plain_output() {
+        true => Box::new(PlainRenderer),
+        false => Box::new(CompactRenderer::new(options.theme)),
+    };
+
+    println!();
+    for line in endless_summary(&rounds, renderer.as_ref()) {
+        println!("{}", line);
+    }
+
+    if run == rounds.len() {
+        println!("You solved every word in the list!");
+    }
+
+    println!("Words solved in a row: {}", run);
+
+    if stats::update(|stats| stats.record_endless_run(run))? {
+        println!("New best run!");
+    }
+
+    Ok(())
+}
+
+/// Returns the lines summarizing each round of an endless run, with the answer and how many guesses
+/// it took on one line and the guesses on the next
+///
+/// Parameters
+/// rounds:     The games played in each round
+/// renderer:   The renderer to draw the guesses with, which should draw each guess on one line
+pub fn endless_summary(rounds: &[Game], renderer: &dyn Renderer) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+
+    for (i, game) in rounds.iter().enumerate() {
+        let result = match game.is_won() {
+            true => format!("{}/{}", game.guesses().len(), game.max_guesses()),
+            false => format!("X/{}", game.max_guesses()),
+        };
+
+        lines.push(format!(
+            "Round {}: {} {}",
+            i + 1,
+            game.word_to_guess(),
+            result
+        ));
+
+        let guesses: Vec<String> = game
+            .guesses()
+            .iter()
+            .flat_map(|word| renderer.render_guess(word))
+            .collect();
+
+        lines.push(format!("  {}", guesses.join("  ")));
+    }
+
+    lines
+}
+
+/// Plays a game with a person, either full screen or as plain text, then leaves the result on the
+/// terminal
+///
+/// Parameters
+/// game:           The game to play
+/// options:        The options to play the game with
+/// timer:          The clock the game is played against
+/// definitions:    The definitions to explain the answer with, if there are any
+/// title:          A line to show above the game, such as which round of a run it is
+fn play_human(
+    game: &mut Game,
+    options: &GameOptions,
+    timer: &mut Timer,
+    definitions: Option<&Definitions>,
+    title: Option<&str>,
+) -> Result<(), Errors> {
+    // Falling back to plain text when the output can't show colours or move the cursor
+    if plain_output() {
+        colored::control::set_override(false);
+
+        if let Some(title) = title {
+            println!("{}", title);
+        }
+
+        play_plain(game, options, timer).map_err(terminal_error)?;
+    } else {
+        // The title is drawn in the full screen game, as anything printed before it is hidden
+        tui::run(game, options, timer, title).map_err(terminal_error)?;
+
+        // The full screen game is gone once it ends, so the final grid is printed again to leave
+        // the result on the terminal
+        let compact = compact_layout(options.compact, game.max_guesses() * 3 + 1);
 
-        // Displaying the word grid to the console
-        display_game_state(&guesses);
+        display_game_state(&[&*game], grid_renderer(options.theme, compact).as_ref())
+            .map_err(terminal_error)?;
+    }
+
+    // The game may have been quit part way through, which also stops the clock
+    timer.stop();
+
+    if game.is_timed_out() {
+        println!("Time's up!");
+    }
+
+    println!("The word was {}", game.word_to_guess().bold());
+
+    if let Some(definition) = definitions.and_then(|d| d.get(game.word_to_guess())) {
+        println!("{}", definition.italic());
+    }
+
+    // The true feedback is only shown once the game is over, so the lies can be checked
+    if game.scoring() == Scoring::Fibble && !game.guesses().is_empty() {
+        let renderer: Box<dyn Renderer> = match plain_output() {
+            true => Box::new(PlainRenderer),
+            false => Box::new(CompactRenderer::new(options.theme)),
+        };
+
+        println!();
+        for line in lie_summary(game, renderer.as_ref()) {
+            println!("{}", line);
+        }
+        println!();
+    }
+
+    if game.max_hints() > 0 {
+        println!("Hints used: {} of {}", game.hints().len(), game.max_hints());
+    }
+
+    if options.analysis && !game.guesses().is_empty() {
+        println!();
+        for guess in analysis::analyse(game) {
+            println!("{}", guess);
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the lines showing the true feedback of each guess in a game where the scoring lies,
+/// with the letter that was marked wrongly
+///
+/// Parameters
+/// game:       The game that was played
+/// renderer:   The renderer to draw the guesses with, which should draw each guess on one line
+pub fn lie_summary(game: &Game, renderer: &dyn Renderer) -> Vec<String> {
+    let mut lines = vec![String::from("The true colours were")];
+
+    for word in game.guesses() {
+        let row = renderer.render_guess(&word.revealed()).join(" ");
+
+        match word.lie() {
+            Some(index) => lines.push(format!("{}   letter {} lied", row, index + 1)),
+            None => lines.push(row),
+        }
+    }
+
+    lines
+}
+
+/// Returns the definitions file the options point to, read before the game starts so a broken file
+/// is caught straight away
+///
+/// Parameters
+/// options:    The options the game is played with
+fn load_definitions(options: &GameOptions) -> Result<Option<Definitions>, Errors> {
+    options
+        .definitions
+        .as_deref()
+        .map(Definitions::load)
+        .transpose()
+}
+
+/// Records a finished game in the stats and history, telling the player how long it took if they
+/// won
+///
+/// Parameters
+/// game:   The game that was played
+/// timer:  The clock the game was played against
+/// mode:   The mode the game was played in
+fn record_game(game: &Game, timer: &Timer, mode: Mode) -> Result<(), Errors> {
+    // Games that were quit part way through don't count
+    if !game.is_over() {
+        return Ok(());
+    }
+
+    history::append(&HistoryEntry::new(game, timer, mode))?;
+
+    // Practice games are kept in the history, but don't count towards the stats
+    if mode == Mode::Practice {
+        return Ok(());
+    }
+
+    let personal_best = stats::update(|stats| {
+        stats.hints_used += game.hints().len() as u64;
+        stats.record_game(game.is_won(), timer.elapsed())
+    })?;
+
+    if game.is_won() {
+        println!("Solved in {}", format_duration(timer.elapsed()));
+
+        if personal_best {
+            println!("New personal best!");
+        }
+    }
+
+    Ok(())
+}
+
+/// Plays a duel, where two players take turns to guess the same word on their own boards
+///
+/// Parameters
+/// options:    The options to play the duel with
+/// names:      The names of the two players, in the order they take turns
+pub fn play_duel(options: GameOptions, names: [String; 2]) -> Result<(), Errors> {
+    let (words_to_guess, word_to_guess) = words_and_answer(&options)?;
+
+    let mut duel = Duel::new(names, word_to_guess, words_to_guess, &options);
+
+    if plain_output() {
+        colored::control::set_override(false);
+
+        play_duel_plain(&mut duel, options.show_word).map_err(terminal_error)?;
+    } else {
+        tui::run_duel(&mut duel, &options).map_err(terminal_error)?;
+
+        let compact = compact_layout(options.compact, options.max_guesses * 3 + 2);
+        let games: Vec<&Game> = duel.players().iter().map(|player| player.game()).collect();
+
+        display_game_state(&games, grid_renderer(options.theme, compact).as_ref())
+            .map_err(terminal_error)?;
+    }
+
+    println!("{}", duel.summary());
+    println!("The word was {}", duel.word_to_guess().bold());
+
+    Ok(())
+}
+
+/// Plays the game with plain text input and output, printing each guess once as it is made. A
+/// countdown can't interrupt the player while they type, so it is checked once they enter a guess
+///
+/// Parameters
+/// game:       The game to play
+/// options:    The options to play the game with
+/// timer:      The clock the game is played against
+fn play_plain(game: &mut Game, options: &GameOptions, timer: &mut Timer) -> io::Result<()> {
+    // Printing out the word for testing purposes
+    if options.show_word {
+        println!("{}", game.word_to_guess());
+    }
+
+    if game.max_hints() > 0 {
+        println!("{}", hint::help_line(game.hints_left()));
+    }
+
+    while !game.is_over() {
+        if options.stopwatch || timer.has_limit() {
+            println!("{}", timer.clock_line());
+        }
 
         // Prompt for the user to type their guess
-        execute!(stdout(), Print("> "),).unwrap();
+        execute!(stdout(), Print("> "),)?;
 
-        // Getting the users input from the player and trimming it
         let mut guess = String::new();
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Could not read the line");
-        let guess = guess.trim().to_string();
-
-        // If the player guessed the word, then the game is over
-        if guess == word_to_guess {
-            game_over = true;
-        }
-
-        // Creating a Word struct to store the players guess
-        let guessed_word = Word::new(guess, &word_to_guess, &words_to_guess);
-
-        // Adding the gussed word to the guesses vec if it was created successfully, otherwise
-        // printing an appropriate error message for the player
-        match guessed_word {
-            Ok(word) => guesses.push(word),
-            Err(err) => {
-                match err {
-                    BuildErrors::TooLongOfWord => {
-                        println!("Words cannot be longer than 5 letters!")
-                    }
-                    BuildErrors::TooShortOfWord => {
-                        println!("Words cannot be shorter than 5 letters!")
-                    }
-                    BuildErrors::NonAlphabeticCharcter => {
-                        println!("Words can only contain alphabetic characters!")
-                    }
-                    BuildErrors::NonValidWord => {
-                        println!("That is not a valid guess!")
-                    }
-                }
+        let bytes_read = io::stdin().read_line(&mut guess)?;
 
-                // Pausing for the player to be able to read the message
-                io::stdin()
-                    .read_line(&mut String::new())
-                    .expect("Could not read the line");
-                // Moves the cursor back the lines we just created
-                execute!(stdout(), cursor::MoveUp(2)).unwrap();
-            }
+        // If the input has ended, such as when it is piped in, there are no more guesses to read
+        if bytes_read == 0 {
+            println!();
+            break;
+        }
+
+        if timer.is_expired() {
+            game.time_out();
+            break;
         }
 
-        // Move the cursor back to the saved position in prep for clearing the screen
-        execute!(stdout(), cursor::MoveUp(19)).unwrap();
+        let key = match guess.trim().chars().collect::<Vec<char>>()[..] {
+            [key] => Some(key),
+            _ => None,
+        };
 
-        // If the player has had more than the max guesses then the game is also over
-        if guesses.len() >= MAX_GUESSES {
-            game_over = true;
+        // In practice a line with just the list key lists the words that are still possible
+        if options.practice && key == Some(LIST_KEY) {
+            for row in candidate_rows(&solver::remaining(game.words(), game.guesses()), 8) {
+                println!("{}", row);
+            }
+            continue;
         }
 
-        // If the game is over, then the loop ends and the user wont see the word grid with their
-        // correct guess, so we print it here
-        if game_over {
-            execute!(
-                stdout(),
-                terminal::Clear(terminal::ClearType::FromCursorDown)
-            )
-            .unwrap();
+        // A line with just a hint key asks for a hint instead of making a guess
+        if let Some(kind) = key.and_then(HintKind::from_key) {
+            let hints_left = game.hints_left();
 
-            display_game_state(&guesses);
+            match game.hint(kind) {
+                Some(hint) => println!("{} ({} left)", hint, hints_left - 1),
+                None => println!("{}", hint::no_hint_message(hints_left)),
+            }
+            continue;
+        }
 
-            println!("The word was {}", word_to_guess.bold());
+        match game.guess(&guess) {
+            Ok(word) => {
+                println!("{}", PlainRenderer.render_guess(word).join("\n"));
+                timer.next_guess();
+
+                if options.practice && !game.is_over() {
+                    let left = solver::remaining(game.words(), game.guesses()).len();
+                    println!(
+                        "{} (enter {} to list them)",
+                        words_left_line(left),
+                        LIST_KEY
+                    );
+                }
+            }
+            Err(err) => println!("{}", build_error_message(&err, game.word_length())),
         }
     }
 
     Ok(())
 }
 
-/// Prints the current word grid to the console
+/// Plays a duel with plain text input and output, prompting each player by name on their turn
 ///
 /// Parameters
-/// guesses:    The Vec containg the players guesses
-pub fn display_game_state(guesses: &Vec<Word>) {
-    for word in guesses {
-        word.print();
+/// duel:       The duel to play
+/// show_word:  Whether to print the word to guess
+fn play_duel_plain(duel: &mut Duel, show_word: bool) -> io::Result<()> {
+    if show_word {
+        println!("{}", duel.word_to_guess());
     }
 
-    if guesses.len() < MAX_GUESSES {
-        let boxes_left = MAX_GUESSES - guesses.len();
+    let mut turn_started = Instant::now();
+
+    while !duel.is_over() {
+        execute!(stdout(), Print(format!("{} > ", duel.current().name())))?;
+
+        let mut guess = String::new();
+        let bytes_read = io::stdin().read_line(&mut guess)?;
 
-        if boxes_left > 0 {
-            for _i in 0..boxes_left {
-                print_blank_boxes();
+        if bytes_read == 0 {
+            println!();
+            break;
+        }
+
+        // A line with just a hint key asks for a hint for the player whose turn it is
+        if let [key] = guess.trim().chars().collect::<Vec<char>>()[..] {
+            if let Some(kind) = HintKind::from_key(key) {
+                let hints_left = duel.current().game().hints_left();
+
+                match duel.hint(kind) {
+                    Some(hint) => println!("{} ({} left)", hint, hints_left - 1),
+                    None => println!("{}", hint::no_hint_message(hints_left)),
+                }
+                continue;
+            }
+        }
+
+        let word_length = duel.current().game().word_length();
+
+        match duel.guess(&guess, turn_started.elapsed()) {
+            Ok(word) => {
+                println!("{}", PlainRenderer.render_guess(word).join("\n"));
+                turn_started = Instant::now();
             }
+            Err(err) => println!("{}", build_error_message(&err, word_length)),
         }
     }
+
+    Ok(())
 }
 
-/// Prints a blank word to the console, used so that there is always a 5x6 grid on the screen
-fn print_blank_boxes() {
-    execute!(
-        stdout(),
-        Print("╭───╮╭───╮╭───╮╭───╮╭───╮\n"),
-        Print("│   ││   ││   ││   ││   │\n"),
-        Print("╰───╯╰───╯╰───╯╰───╯╰───╯\n"),
-    )
-    .unwrap();
+/// Returns the error for the terminal failing part way through a game, such as when it is closed
+///
+/// Parameters
+/// err:    The error the terminal gave
+fn terminal_error(err: io::Error) -> Errors {
+    Errors::Terminal(err.to_string())
+}
+
+/// Returns the message to show the player when their guess isn't valid
+///
+/// Parameters
+/// err:            The reason the guess isn't valid
+/// word_length:    The length of the word to guess
+pub fn build_error_message(err: &BuildErrors, word_length: usize) -> String {
+    match err {
+        BuildErrors::TooLongOfWord => {
+            format!("Words cannot be longer than {} letters!", word_length)
+        }
+        BuildErrors::TooShortOfWord => {
+            format!("Words cannot be shorter than {} letters!", word_length)
+        }
+        BuildErrors::NonAlphabeticCharcter => {
+            String::from("Words can only contain alphabetic characters!")
+        }
+        BuildErrors::NonValidWord => String::from("That is not a valid guess!"),
+        BuildErrors::IgnoredHint => {
+            String::from("Guesses must use every hint revealed so far in hard mode!")
+        }
+    }
+}
+
+/// The key that lists the words that are still possible in practice
+pub const LIST_KEY: char = '=';
+
+/// Returns the line telling the player how many words are still possible
+///
+/// Parameters
+/// count:  The number of words that are still possible
+pub fn words_left_line(count: usize) -> String {
+    match count {
+        1 => String::from("1 word left"),
+        count => format!("{} words left", count),
+    }
+}
+
+/// Returns the words that are still possible laid out in rows, so they can be listed without one
+/// word per line
+///
+/// Parameters
+/// words:      The words that are still possible
+/// per_row:    The number of words on each row
+pub fn candidate_rows(words: &[String], per_row: usize) -> Vec<String> {
+    words
+        .chunks(per_row.max(1))
+        .map(|row| row.join(" ").to_uppercase())
+        .collect()
+}
+
+/// Returns whether the game should be drawn as plain text, which is the case when stdout is not a
+/// terminal or the NO_COLOR environment variable is set
+pub fn plain_output() -> bool {
+    let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+
+    no_color || !stdout().is_terminal()
+}
+
+/// Returns whether the compact layout should be used, which is either because it was asked for or
+/// because the full layout is too tall for the terminal
+///
+/// Parameters
+/// compact:        Whether the compact layout was asked for, or None to decide automatically
+/// full_height:    The number of lines the full layout needs
+pub fn compact_layout(compact: Option<bool>, full_height: usize) -> bool {
+    match compact {
+        Some(compact) => compact,
+        None => match terminal::size() {
+            Ok((_, rows)) => (rows as usize) < full_height,
+            Err(_) => false,
+        },
+    }
+}
+
+/// Prints the current word grid of each game to the console, next to each other
+///
+/// Parameters
+/// games:      The games to print
+/// renderer:   The renderer to draw the games with
+pub fn display_game_state(games: &[&Game], renderer: &dyn Renderer) -> io::Result<()> {
+    let mut display = String::new();
+
+    let boards: Vec<Vec<String>> = games
+        .iter()
+        .map(|game| renderer.render(game, None))
+        .collect();
+
+    for row in side_by_side(&boards, 4) {
+        display.push_str(&row);
+        display.push('\n');
+    }
+
+    execute!(stdout(), Print(display))
+}
+
+/// Returns the words of the given length from a word list, or from the built in list if no word
+/// list is given
+///
+/// Parameters
+/// word_list:      The path to the word list to read, if any
+/// word_length:    The length of the words to keep
+pub fn load_words(word_list: Option<String>, word_length: usize) -> Result<Vec<String>, Errors> {
+    let mut words = match word_list {
+        Some(file) => lines_from_file(file)?,
+        None => DEFAULT_WORDS.iter().map(|x| x.to_string()).collect(),
+    };
+
+    // Only words of the configured length can be guessed
+    words.retain(|word| word.chars().count() == word_length);
+
+    if words.is_empty() {
+        return Err(Errors::NoWordsOfLength(word_length));
+    }
+
+    Ok(words)
 }
 
 /// Reads the lines from an input file into a Vec of strings
@@ -182,7 +794,8 @@ fn lines_from_file(filename: impl AsRef<Path>) -> Result<Vec<String>, Errors> {
 
     for line in BufReader::new(file).lines() {
         match line {
-            Ok(line) => lines.push(line),
+            // Case folding the words so that they match the case folded guesses
+            Ok(line) => lines.push(line.trim().to_lowercase()),
             Err(_) => continue,
         }
     }
@@ -194,16 +807,104 @@ fn lines_from_file(filename: impl AsRef<Path>) -> Result<Vec<String>, Errors> {
     Ok(lines)
 }
 
+/// Returns the word list to guess from and the word to guess
+///
+/// Parameters
+/// options:    The options the game is played with
+fn words_and_answer(options: &GameOptions) -> Result<(Vec<String>, String), Errors> {
+    // A chosen word decides the length of the guesses, rather than the configured word length
+    let word_length = match &options.answer {
+        AnswerChoice::Fixed(word) => word.trim().chars().count(),
+        _ => options.word_length,
+    };
+
+    let words = load_words(options.word_list.clone(), word_length)?;
+
+    let answer = match &options.answer {
+        AnswerChoice::Random => {
+            let pool = answer_pool(&words, options.difficulty);
+
+            // Games played by scripts and bots don't use up the answers the player hasn't seen
+            match options.protocol {
+                Protocol::Json => random_word(&pool),
+                Protocol::Human => fresh_word(&pool)?,
+            }
+        }
+        AnswerChoice::Daily => daily_word(&words, days_since_epoch()),
+        AnswerChoice::Fixed(word) => check_answer(word, &words)?,
+    };
+
+    Ok((words, answer))
+}
+
+/// Returns the words random answers are drawn from, which is the band of the word list for the
+/// difficulty if there is one
+///
+/// Parameters
+/// words:      The word list
+/// difficulty: How hard the answers should be, if it matters
+pub fn answer_pool(words: &[String], difficulty: Option<Difficulty>) -> Vec<String> {
+    match difficulty {
+        Some(difficulty) => difficulty::band(words, difficulty),
+        None => words.to_vec(),
+    }
+}
+
+/// Returns a random answer that hasn't been served before, remembering it so it isn't served again
+/// until every other word in the pool has been
+///
+/// Parameters
+/// pool:   The words the answer can be drawn from
+pub fn fresh_word(pool: &[String]) -> Result<String, Errors> {
+    served::update(|served| served.pick(pool))
+}
+
+/// Returns a word someone chose as the answer, checking that it is in the word list so that it can
+/// be guessed
+///
+/// Parameters
+/// word:   The chosen word
+/// words:  The word list the word has to be in
+pub fn check_answer(word: &str, words: &[String]) -> Result<String, Errors> {
+    let word = word.trim().to_lowercase();
+
+    match words.contains(&word) {
+        true => Ok(word),
+        false => Err(Errors::WordNotInList(word)),
+    }
+}
+
 /// Chooses a random word
 ///
 /// Parameters
 /// word_list:  The vec of String to choose a word from
-fn random_word(word_list: &Vec<String>) -> String {
+fn random_word(word_list: &[String]) -> String {
     let random_index: usize = rand::thread_rng().gen_range(0..word_list.len());
 
     word_list[random_index].clone()
 }
 
+/// Returns the number of whole days since the unix epoch in UTC, which is used to decide the word
+/// of the day
+pub fn days_since_epoch() -> u64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs() / 86_400,
+        Err(_) => 0,
+    }
+}
+
+/// Chooses the word of the day. The words are spread out over the list so that consecutive days
+/// don't get words from the same part of the list
+///
+/// Parameters
+/// word_list:  The vec of String to choose a word from
+/// day:        The day to choose the word for, as the number of days since the unix epoch
+fn daily_word(word_list: &[String], day: u64) -> String {
+    let index = day.wrapping_mul(2_654_435_761) % word_list.len() as u64;
+
+    word_list[index as usize].clone()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -233,4 +934,63 @@ mod tests {
 
         assert!(!(word_one == word_two))
     }
+
+    #[test]
+    /// Checks if the daily_word function picks the same word for the same day, and a different
+    /// word for the next day
+    fn daily_word_is_stable() {
+        let word_list: Vec<String> = DEFAULT_WORDS.iter().map(|x| x.to_string()).collect();
+
+        assert!(daily_word(&word_list, 19_650) == daily_word(&word_list, 19_650));
+        assert!(daily_word(&word_list, 19_650) != daily_word(&word_list, 19_651));
+    }
+
+    #[test]
+    /// Checks if the endless summary has a line for each round's result and one for its guesses
+    fn endless_summary_describes_each_round() {
+        let words = vec![String::from("cigar"), String::from("crane")];
+
+        let mut solved = Game::new(String::from("cigar"), words.clone(), 6, false);
+        solved.guess("crane").unwrap();
+        solved.guess("cigar").unwrap();
+
+        let mut failed = Game::new(String::from("crane"), words, 1, false);
+        failed.guess("cigar").unwrap();
+
+        let lines = endless_summary(&[solved, failed], &PlainRenderer);
+
+        assert!(lines.len() == 4);
+        assert!(lines[0] == "Round 1: cigar 2/6");
+        assert!(lines[1] == "  [C](R)(A)ne  [C][I][G][A][R]");
+        assert!(lines[2] == "Round 2: crane X/1");
+    }
+
+    #[test]
+    /// Checks if the lie summary shows the true feedback of each guess and where the lie was
+    fn lie_summary_reveals_lies() {
+        let words = vec![String::from("cigar"), String::from("crane")];
+
+        let mut game =
+            Game::new(String::from("cigar"), words, 6, false).with_scoring(Scoring::Fibble);
+        game.guess("crane").unwrap();
+
+        let lie = game.guesses()[0].lie().unwrap();
+        let lines = lie_summary(&game, &PlainRenderer);
+
+        assert!(lines.len() == 2);
+        assert!(lines[1] == format!("[C](R)(A)ne   letter {} lied", lie + 1));
+    }
+
+    #[test]
+    /// Checks if the words left in practice are laid out in rows and counted with the right plural
+    fn candidates_are_listed_in_rows() {
+        let words: Vec<String> = ["cigar", "crane", "clear"]
+            .iter()
+            .map(|word| word.to_string())
+            .collect();
+
+        assert!(candidate_rows(&words, 2) == vec!["CIGAR CRANE", "CLEAR"]);
+        assert!(words_left_line(1) == "1 word left");
+        assert!(words_left_line(3) == "3 words left");
+    }
 }
@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+
+use crate::wordlist::DEFAULT_WORDS;
+use crate::Errors;
+
+/// Enum for the problems a line in a word list can have
+#[derive(Debug, PartialEq, Eq)]
+pub enum LintIssue {
+    EmptyLine,
+    SurroundingWhitespace,
+    Uppercase,
+    NonAlphabeticCharacter,
+    WrongLength(usize),
+    Duplicate(usize),
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LintIssue::EmptyLine => write!(f, "empty line"),
+            LintIssue::SurroundingWhitespace => write!(f, "leading or trailing whitespace"),
+            LintIssue::Uppercase => write!(f, "contains uppercase letters"),
+            LintIssue::NonAlphabeticCharacter => write!(f, "contains non-alphabetic characters"),
+            LintIssue::WrongLength(length) => write!(f, "is {} letters long", length),
+            LintIssue::Duplicate(line) => write!(f, "duplicate of line {}", line),
+        }
+    }
+}
+
+/// Struct to represent a problem found on a line of a word list
+#[derive(Debug, PartialEq, Eq)]
+pub struct LintWarning {
+    pub line: usize,
+    pub word: String,
+    pub issue: LintIssue,
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {:?} {}", self.line, self.word, self.issue)
+    }
+}
+
+/// Returns the lines of a word list as they are in the file, or the built in list if no word list
+/// is given
+///
+/// Parameters
+/// word_list:  The path to the word list to read, if any
+pub fn read_raw_lines(word_list: Option<String>) -> Result<Vec<String>, Errors> {
+    match word_list {
+        Some(file) => match fs::read_to_string(file) {
+            Ok(contents) => Ok(contents.lines().map(|line| line.to_string()).collect()),
+            Err(_) => Err(Errors::FailedToOpenFile),
+        },
+        None => Ok(DEFAULT_WORDS.iter().map(|x| x.to_string()).collect()),
+    }
+}
+
+/// Checks the lines of a word list for words that can't be guessed, or that would be read
+/// differently to how they are written
+///
+/// Parameters
+/// lines:          The lines of the word list
+/// word_length:    The length every word should be
+pub fn lint_lines(lines: &[String], word_length: usize) -> Vec<LintWarning> {
+    let mut warnings: Vec<LintWarning> = Vec::new();
+
+    // Storing the first line each word was seen on to point duplicates back to it
+    let mut first_seen: HashMap<String, usize> = HashMap::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        let line_number = index + 1;
+        let word = line.trim();
+
+        let mut warn = |issue: LintIssue| {
+            warnings.push(LintWarning {
+                line: line_number,
+                word: line.clone(),
+                issue,
+            })
+        };
+
+        if word.is_empty() {
+            warn(LintIssue::EmptyLine);
+            continue;
+        }
+
+        if word != line {
+            warn(LintIssue::SurroundingWhitespace);
+        }
+
+        if word.chars().any(|letter| letter.is_uppercase()) {
+            warn(LintIssue::Uppercase);
+        }
+
+        if !word.chars().all(|letter| letter.is_alphabetic()) {
+            warn(LintIssue::NonAlphabeticCharacter);
+        }
+
+        let length = word.chars().count();
+        if length != word_length {
+            warn(LintIssue::WrongLength(length));
+        }
+
+        // Words are compared the same way the game compares them, ignoring case
+        match first_seen.get(&word.to_lowercase()) {
+            Some(first_line) => warn(LintIssue::Duplicate(*first_line)),
+            None => {
+                first_seen.insert(word.to_lowercase(), line_number);
+            }
+        }
+    }
+
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Checks if a clean word list has no warnings
+    fn clean_list_has_no_warnings() {
+        let lines = vec![String::from("crane"), String::from("moist")];
+
+        assert!(lint_lines(&lines, 5).is_empty());
+    }
+
+    #[test]
+    /// Checks if each kind of problem is found on the right line
+    fn finds_every_issue() {
+        let lines = vec![
+            String::from("crane"),
+            String::from(""),
+            String::from(" moist"),
+            String::from("Guess"),
+            String::from("it's!"),
+            String::from("spread"),
+            String::from("CRANE"),
+        ];
+
+        let warnings = lint_lines(&lines, 5);
+
+        let expected = [
+            (2, "", LintIssue::EmptyLine),
+            (3, " moist", LintIssue::SurroundingWhitespace),
+            (4, "Guess", LintIssue::Uppercase),
+            (5, "it's!", LintIssue::NonAlphabeticCharacter),
+            (6, "spread", LintIssue::WrongLength(6)),
+            (7, "CRANE", LintIssue::Uppercase),
+            (7, "CRANE", LintIssue::Duplicate(1)),
+        ];
+
+        // The warnings are compared as a set, so the order they are found in doesn't matter
+        assert!(warnings.len() == expected.len());
+        for (line, word, issue) in expected {
+            assert!(warnings.contains(&LintWarning {
+                line,
+                word: String::from(word),
+                issue,
+            }));
+        }
+    }
+}
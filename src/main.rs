@@ -1,27 +1,716 @@
-use clap::Parser;
+use std::time::Duration;
 
-use wordle_cli::Errors;
+use clap::{Args, Parser, Subcommand};
+
+use wordle_cli::code;
+use wordle_cli::config::{self, PartialSettings, Settings};
+use wordle_cli::definitions::Definitions;
+use wordle_cli::difficulty::Difficulty;
+use wordle_cli::history;
+use wordle_cli::lint;
+use wordle_cli::multiplayer;
+use wordle_cli::protocol::Protocol;
+use wordle_cli::render::{BoxRenderer, PlainRenderer, Renderer};
+use wordle_cli::scoring::Scoring;
+use wordle_cli::served;
+use wordle_cli::solver;
+use wordle_cli::stats;
+use wordle_cli::theme::{Palette, Theme};
+use wordle_cli::word::Word;
+use wordle_cli::wordstats::WordStats;
+use wordle_cli::{AnswerChoice, Errors, GameOptions};
+
+/// The number of rows of words the solve command lists before cutting the list short
+const SOLVE_ROWS: usize = 10;
 
 #[derive(Parser, Debug)]
 /// Play wordle in your terminal!
-#[clap(author, version, about)]
-pub struct Args {
-    /// The text file containg the word list to use
-    file_path: Option<String>,
+#[clap(author, version, about, args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Options for playing a game, used when no command is given
+    #[command(flatten)]
+    play: PlayArgs,
+}
+
+impl Cli {
+    /// Returns the command to run, which is play when no command was given
+    fn into_command(self) -> Command {
+        self.command.unwrap_or(Command::Play(self.play))
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Play a game with a random word (the default command)
+    Play(PlayArgs),
+    /// Play the word of the day, which is the same for everyone using the same word list
+    Daily(PlayArgs),
+    /// Play several puzzles one after another against the clock
+    Speedrun(SpeedrunArgs),
+    /// Keep playing new words until you fail to solve one
+    Endless(RoundArgs),
+    /// Play a game that shows how many words are still possible after each guess, and lists them
+    /// when you press =
+    Practice(RoundArgs),
+    /// Two players take turns to guess the same word on their own boards, and whoever solves it
+    /// in fewer guesses wins, with ties broken by time
+    Duel(DuelArgs),
+    /// Check a word list for words that can't be guessed
+    Lint(SettingsArgs),
+    /// Show the words that fit the feedback your guesses got, and the guess the solver would play
+    /// next
+    Solve(SolveArgs),
+    /// Show letter frequencies, common patterns, double letters and the best starting words of a
+    /// word list
+    Wordstats(SettingsArgs),
+    /// Show the settings the game would be played with, and where each one came from
+    Config(SettingsArgs),
+    /// Show how many games you have played and won, and your fastest times
+    Stats,
+    /// Step through a finished game one guess at a time
+    Replay(ReplayArgs),
+    /// Show how many answers have been served, which won't come up again until the rest of the
+    /// word list has
+    Served(ServedArgs),
+    /// Make a code for a word you choose, to challenge someone else to guess it
+    Create(CreateArgs),
+    /// Look up the definition of a word in the list, from the definitions file
+    Define(DefineArgs),
+    /// Host a word for players on the local network to race on
+    Serve(ServeArgs),
+    /// Join a race hosted with the serve command
+    Join(JoinArgs),
+}
+
+#[derive(Args, Debug)]
+struct PlayArgs {
+    #[command(flatten)]
+    round: RoundArgs,
+
+    #[arg(long)]
+    /// Play the word a code made with the create command stands for
+    code: Option<String>,
+
+    #[arg(long, value_enum, default_value_t = Protocol::Human)]
+    /// How to talk to whoever is playing, with json reading guesses from stdin and writing one
+    /// JSON object per line to stdout
+    protocol: Protocol,
+}
+
+#[derive(Args, Debug)]
+struct SpeedrunArgs {
+    #[command(flatten)]
+    round: RoundArgs,
+
+    #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u16).range(1..))]
+    /// The number of puzzles to play
+    puzzles: u16,
+}
+
+#[derive(Args, Debug)]
+struct RoundArgs {
+    #[command(flatten)]
+    settings: SettingsArgs,
 
     #[arg(long)]
     /// Whether to show the word to guess or not
     show_word: bool,
+
+    #[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    /// How long the whole game can take before it is lost
+    time_limit: Option<u64>,
+
+    #[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    /// How long each guess can take before the game is lost
+    guess_time_limit: Option<u64>,
+}
+
+impl RoundArgs {
+    /// Returns the options to play each game with, merging the settings with the config file
+    fn into_game_options(self) -> Result<GameOptions, Errors> {
+        let settings = load_settings(self.settings)?;
+
+        Ok(GameOptions {
+            show_word: self.show_word,
+            time_limit: self.time_limit.map(Duration::from_secs),
+            guess_time_limit: self.guess_time_limit.map(Duration::from_secs),
+            ..settings.game_options()
+        })
+    }
+}
+
+#[derive(Args, Debug)]
+struct DuelArgs {
+    #[command(flatten)]
+    settings: SettingsArgs,
+
+    #[arg(long)]
+    /// Whether to show the word to guess or not
+    show_word: bool,
+
+    #[arg(long, num_args = 2, value_names = ["PLAYER_ONE", "PLAYER_TWO"])]
+    /// The names of the two players, in the order they take turns [default: Player 1, Player 2]
+    players: Option<Vec<String>>,
+}
+
+#[derive(Args, Debug)]
+struct SolveArgs {
+    #[command(flatten)]
+    settings: SettingsArgs,
+
+    #[arg(long = "guess", value_name = "ROW")]
+    /// A guess and the feedback it got, with letters in the right place written as [A], letters
+    /// in the wrong place as (A) and the rest as a, such as [C](R)ane. Can be given more than once
+    guesses: Vec<String>,
+}
+
+#[derive(Args, Debug)]
+struct ServeArgs {
+    #[command(flatten)]
+    settings: SettingsArgs,
+
+    #[arg(long, default_value = multiplayer::DEFAULT_ADDRESS)]
+    /// The address to listen for players on, such as 0.0.0.0:7777 to let in other computers
+    address: String,
+
+    #[arg(long)]
+    /// Whether to show the word to guess on the server or not
+    show_word: bool,
+}
+
+#[derive(Args, Debug)]
+struct JoinArgs {
+    /// The address of the server, such as 192.168.1.20:7777
+    address: String,
+
+    #[arg(long)]
+    /// The name to race under [default: your user name]
+    name: Option<String>,
+
+    #[command(flatten)]
+    theme: ThemeArgs,
+}
+
+#[derive(Args, Debug)]
+struct ServedArgs {
+    #[arg(long)]
+    /// Forget the answers served so far, so any word can come up again
+    reset: bool,
+}
+
+#[derive(Args, Debug)]
+struct CreateArgs {
+    /// The word to challenge someone to guess
+    word: String,
+
+    #[command(flatten)]
+    settings: SettingsArgs,
+}
+
+#[derive(Args, Debug)]
+struct DefineArgs {
+    /// The word to look up
+    word: String,
+
+    #[command(flatten)]
+    settings: SettingsArgs,
+}
+
+#[derive(Args, Debug)]
+struct ReplayArgs {
+    /// The number of the game to replay, as shown by --list [default: the latest game]
+    game: Option<usize>,
+
+    #[arg(long)]
+    /// List every game in the history instead of replaying one
+    list: bool,
+
+    #[command(flatten)]
+    theme: ThemeArgs,
+}
+
+#[derive(Args, Debug)]
+struct ThemeArgs {
+    #[arg(long, value_enum)]
+    /// The colour palette to draw the letters with [default: classic]
+    theme: Option<Palette>,
+
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    /// Whether to also mark each letter with a symbol, so the game can be played without colour
+    symbols: Option<bool>,
+}
+
+impl ThemeArgs {
+    /// Returns the theme to draw with, merging the options given with the config file. Only the
+    /// theme is taken from the config file, as nothing else applies
+    fn into_theme(self) -> Result<Theme, Errors> {
+        let settings = load_config_file(PartialSettings {
+            theme: self.theme,
+            symbols: self.symbols,
+            ..PartialSettings::default()
+        })?;
+
+        Ok(settings.game_options().theme)
+    }
+}
+
+#[derive(Args, Debug)]
+struct SettingsArgs {
+    /// The text file containg the word list to use
+    file_path: Option<String>,
+
+    #[arg(long, value_name = "PATH")]
+    /// A file of definitions for the words in the list, with a word, a colon and the definition on
+    /// each line
+    definitions: Option<String>,
+
+    #[arg(long, value_enum)]
+    /// The colour palette to draw the letters with [default: classic]
+    theme: Option<Palette>,
+
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    /// Whether to also mark each letter with a symbol, so the game can be played without colour
+    symbols: Option<bool>,
+
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    /// Whether to animate guesses being revealed
+    animations: Option<bool>,
+
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    /// Whether to show a stopwatch while playing
+    stopwatch: Option<bool>,
+
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    /// Whether to show how much each guess narrowed down the answer once the game is over
+    analysis: Option<bool>,
+
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    /// Whether to draw each guess on one line, for small terminals [default: when the full grid
+    /// doesn't fit]
+    compact: Option<bool>,
+
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    /// Whether guesses have to use every hint revealed so far
+    hard_mode: Option<bool>,
+
+    #[arg(long, value_enum)]
+    /// How guesses are scored, either marking every letter, only counting the letters in the
+    /// right and wrong places like Mastermind, or marking one letter of each guess wrongly
+    /// [default: letters]
+    scoring: Option<Scoring>,
+
+    #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+    /// The number of guesses the player gets [default: 6]
+    max_guesses: Option<u16>,
+
+    #[arg(long)]
+    /// The number of hints the player can ask for during a game [default: 0]
+    hints: Option<u16>,
+
+    #[arg(long, value_enum)]
+    /// Only draw random answers from the easiest, middle or hardest third of the word list
+    /// [default: any word]
+    difficulty: Option<Difficulty>,
+
+    #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+    /// The length of the words to guess [default: 5]
+    word_length: Option<u16>,
+}
+
+impl SettingsArgs {
+    /// Returns the settings given on the command line
+    fn into_partial_settings(self) -> PartialSettings {
+        PartialSettings {
+            word_list: self.file_path,
+            definitions: self.definitions,
+            theme: self.theme,
+            symbols: self.symbols,
+            animations: self.animations,
+            stopwatch: self.stopwatch,
+            analysis: self.analysis,
+            compact: self.compact,
+            hard_mode: self.hard_mode,
+            scoring: self.scoring,
+            max_guesses: self.max_guesses.map(usize::from),
+            hints: self.hints.map(usize::from),
+            difficulty: self.difficulty,
+            word_length: self.word_length.map(usize::from),
+        }
+    }
 }
 
 fn main() {
-    let args = Args::parse();
+    let cli = Cli::parse();
 
-    match wordle_cli::play(args.file_path, args.show_word) {
-        Ok(_) => (),
-        Err(err) => match err {
+    if let Err(err) = run(cli) {
+        match err {
             Errors::NoWordsInFile => println!("No words were found in the file you selected!"),
             Errors::FailedToOpenFile => println!("Could not open the file you selected!"),
-        },
+            Errors::NoWordsOfLength(length) => {
+                println!("No {} letter words were found in the word list!", length)
+            }
+            Errors::InvalidConfig(message) => {
+                println!("Could not read the config file: {}", message)
+            }
+            Errors::FailedToWriteOutput => println!("Could not write the game to the output!"),
+            Errors::Network(message) => println!("Could not connect to the race: {}", message),
+            Errors::Terminal(message) => println!("Could not use the terminal: {}", message),
+            Errors::InvalidStats(message) => println!("Could not update your stats: {}", message),
+            Errors::InvalidCode => {
+                println!("That code is not valid, check it was typed correctly!")
+            }
+            Errors::WordNotInList(word) => println!("{} is not in the word list!", word),
+            Errors::InvalidHistory(message) => {
+                println!("Could not read or write the game history: {}", message)
+            }
+            Errors::InvalidServed(message) => {
+                println!("Could not update the answers already served: {}", message)
+            }
+            Errors::InvalidDefinitions(message) => {
+                println!("Could not read the definitions file: {}", message)
+            }
+        }
+    }
+}
+
+/// Runs the command the player asked for
+///
+/// Parameters
+/// cli:    The parsed command line arguments
+fn run(cli: Cli) -> Result<(), Errors> {
+    match cli.into_command() {
+        Command::Play(args) => play(args, AnswerChoice::Random),
+        Command::Daily(args) => play(args, AnswerChoice::Daily),
+        Command::Endless(args) => wordle_cli::play_endless(args.into_game_options()?),
+        Command::Practice(args) => wordle_cli::play(GameOptions {
+            practice: true,
+            ..args.into_game_options()?
+        }),
+        Command::Speedrun(args) => {
+            wordle_cli::play_speedrun(args.round.into_game_options()?, usize::from(args.puzzles))
+        }
+        Command::Duel(args) => {
+            let settings = load_settings(args.settings)?;
+
+            let names = match args.players {
+                Some(players) => [players[0].clone(), players[1].clone()],
+                None => [String::from("Player 1"), String::from("Player 2")],
+            };
+
+            wordle_cli::play_duel(
+                GameOptions {
+                    show_word: args.show_word,
+                    ..settings.game_options()
+                },
+                names,
+            )
+        }
+        Command::Lint(args) => {
+            let settings = load_settings(args)?;
+
+            let lines = lint::read_raw_lines(settings.word_list.value)?;
+            let warnings = lint::lint_lines(&lines, settings.word_length.value);
+
+            for warning in &warnings {
+                println!("{}", warning);
+            }
+
+            match warnings.len() {
+                0 => println!("No problems found in {} lines", lines.len()),
+                count => println!("{} problems found in {} lines", count, lines.len()),
+            }
+
+            Ok(())
+        }
+        Command::Solve(args) => {
+            let settings = load_settings(args.settings)?;
+            let word_length = settings.word_length.value;
+
+            let words = wordle_cli::load_words(settings.word_list.value, word_length)?;
+            let mut guesses: Vec<Word> = Vec::new();
+
+            for row in &args.guesses {
+                match Word::from_plain(row) {
+                    Some(word) if word.letters().len() == word_length => guesses.push(word),
+                    _ => {
+                        println!("{} is not feedback for a {} letter guess", row, word_length);
+                        println!(
+                            "Write letters in the right place as [A], in the wrong place as (A)"
+                        );
+                        println!("and the rest as a, such as [C](R)ane");
+
+                        return Ok(());
+                    }
+                }
+            }
+
+            let candidates = solver::remaining(&words, &guesses);
+
+            if candidates.is_empty() {
+                println!("No words in the list fit that feedback");
+
+                return Ok(());
+            }
+
+            println!("{}", wordle_cli::words_left_line(candidates.len()));
+
+            // Long lists are cut short, as the suggested guess is what matters early on
+            let rows = wordle_cli::candidate_rows(&candidates, 8);
+
+            for row in rows.iter().take(SOLVE_ROWS) {
+                println!("{}", row);
+            }
+
+            if rows.len() > SOLVE_ROWS {
+                println!("and {} more", candidates.len() - SOLVE_ROWS * 8);
+            }
+
+            if let Some(guess) = solver::suggest(&words, &candidates) {
+                println!("\nSuggested guess: {}", guess.to_uppercase());
+            }
+
+            Ok(())
+        }
+        Command::Wordstats(args) => {
+            let settings = load_settings(args)?;
+
+            let words =
+                wordle_cli::load_words(settings.word_list.value, settings.word_length.value)?;
+
+            print!("{}", WordStats::new(&words));
+
+            Ok(())
+        }
+        Command::Config(args) => {
+            // A broken config file is reported rather than stopping the command, as this is where
+            // the player would look to find out what is wrong with it
+            let config_file = match config::config_path() {
+                Some(path) => match PartialSettings::load(&path) {
+                    Ok(config_file) if path.exists() => {
+                        println!("Config file: {}\n", path.display());
+                        config_file
+                    }
+                    Ok(config_file) => {
+                        println!("Config file: {} (not found)\n", path.display());
+                        config_file
+                    }
+                    Err(Errors::InvalidConfig(message)) => {
+                        println!("Config file: {} (ignored)", path.display());
+                        println!("Could not read the config file: {}\n", message);
+                        PartialSettings::default()
+                    }
+                    Err(err) => return Err(err),
+                },
+                None => {
+                    println!("Config file: none, as HOME is not set\n");
+                    PartialSettings::default()
+                }
+            };
+
+            print!(
+                "{}",
+                Settings::resolve(config_file, args.into_partial_settings())
+            );
+
+            Ok(())
+        }
+        Command::Stats => {
+            match config::data_path(stats::STATS_FILE) {
+                Some(path) => println!("Stats file: {}\n", path.display()),
+                None => println!("Stats file: none, as HOME is not set\n"),
+            }
+
+            print!("{}", stats::load()?);
+
+            Ok(())
+        }
+        Command::Served(args) => {
+            if args.reset {
+                // The answers are only counted if the file can be read, it is reset either way
+                let forgotten = served::load().map(|served| served.words.len());
+                served::reset()?;
+
+                match forgotten {
+                    Ok(forgotten) => println!("Served answers forgotten: {}", forgotten),
+                    Err(_) => println!("Served answers forgotten"),
+                }
+
+                return Ok(());
+            }
+
+            match config::data_path(served::SERVED_FILE) {
+                Some(path) => println!("Served answers file: {}\n", path.display()),
+                None => println!("Served answers file: none, as HOME is not set\n"),
+            }
+
+            println!("Answers served: {}", served::load()?.words.len());
+            println!("They won't come up again until the rest of the word list has");
+
+            Ok(())
+        }
+        Command::Replay(args) => {
+            let history = history::load()?;
+
+            if args.list {
+                for (i, entry) in history.iter().enumerate() {
+                    println!("{:>4}  {}", i + 1, entry);
+                }
+
+                return Ok(());
+            }
+
+            let entry = match args.game {
+                Some(game) => game.checked_sub(1).and_then(|i| history.get(i)),
+                None => history.last(),
+            };
+
+            match entry {
+                Some(entry) => {
+                    let theme = args.theme.into_theme()?;
+
+                    // Replays are drawn the same way the game is, in plain text when needed
+                    let renderer: Box<dyn Renderer> = match wordle_cli::plain_output() {
+                        true => {
+                            colored::control::set_override(false);
+                            Box::new(PlainRenderer)
+                        }
+                        false => Box::new(BoxRenderer::new(theme)),
+                    };
+
+                    history::replay(entry, renderer.as_ref())
+                        .map_err(|_| Errors::FailedToWriteOutput)
+                }
+                None => {
+                    match args.game {
+                        Some(game) => println!("There is no game {} in the history", game),
+                        None => println!("No games have been finished yet"),
+                    }
+
+                    Ok(())
+                }
+            }
+        }
+        Command::Create(args) => {
+            let settings = load_settings(args.settings)?;
+
+            // The word is checked now, so a code is never made for a word that can't be guessed
+            let length = args.word.trim().chars().count();
+            let words = wordle_cli::load_words(settings.word_list.value, length)?;
+            let word = wordle_cli::check_answer(&args.word, &words)?;
+
+            let code = code::encode(&word);
+
+            println!("Your code is {}", code);
+            println!("Play it with: wordle_cli play --code {}", code);
+
+            Ok(())
+        }
+        Command::Define(args) => {
+            let settings = load_settings(args.settings)?;
+
+            let length = args.word.trim().chars().count();
+            let words = wordle_cli::load_words(settings.word_list.value, length)?;
+            let word = wordle_cli::check_answer(&args.word, &words)?;
+
+            let definitions = match settings.definitions.value {
+                Some(path) => Definitions::load(&path)?,
+                None => {
+                    println!("No definitions file is set, use --definitions <PATH> to set one");
+                    return Ok(());
+                }
+            };
+
+            match definitions.get(&word) {
+                Some(definition) => println!("{}: {}", word, definition),
+                None => println!("There is no definition for {}", word),
+            }
+
+            Ok(())
+        }
+        Command::Serve(args) => {
+            let settings = load_settings(args.settings)?;
+
+            multiplayer::host(
+                GameOptions {
+                    show_word: args.show_word,
+                    ..settings.game_options()
+                },
+                &args.address,
+            )
+        }
+        Command::Join(args) => {
+            let name = args
+                .name
+                .or_else(|| std::env::var("USER").ok())
+                .unwrap_or_else(|| String::from("player"));
+
+            multiplayer::join(args.address.as_str(), &name, args.theme.into_theme()?)
+        }
+    }
+}
+
+/// Plays a game with the options given on the command line
+///
+/// Parameters
+/// args:       The options given on the command line
+/// answer:     How the word to guess is chosen
+fn play(args: PlayArgs, answer: AnswerChoice) -> Result<(), Errors> {
+    // A code decides the word to guess, whichever command was used
+    let answer = match args.code {
+        Some(code) => AnswerChoice::Fixed(code::decode(&code).ok_or(Errors::InvalidCode)?),
+        None => answer,
+    };
+
+    wordle_cli::play(GameOptions {
+        answer,
+        protocol: args.protocol,
+        ..args.round.into_game_options()?
+    })
+}
+
+/// Merges the config file with the settings given on the command line
+///
+/// Parameters
+/// args:   The settings given on the command line
+fn load_settings(args: SettingsArgs) -> Result<Settings, Errors> {
+    load_config_file(args.into_partial_settings())
+}
+
+/// Merges the config file with settings given on the command line
+///
+/// Parameters
+/// command_line:   The settings given on the command line
+fn load_config_file(command_line: PartialSettings) -> Result<Settings, Errors> {
+    let config_file = match config::config_path() {
+        Some(path) => PartialSettings::load(&path)?,
+        None => PartialSettings::default(),
     };
+
+    Ok(Settings::resolve(config_file, command_line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Checks if running without a command plays a game with the options given, the same as the
+    /// play command
+    fn no_command_means_play() {
+        let cli = Cli::try_parse_from(["wordle_cli", "words.txt", "--show-word"]).unwrap();
+
+        match cli.into_command() {
+            Command::Play(args) => {
+                assert!(args.round.settings.file_path == Some(String::from("words.txt")));
+                assert!(args.round.show_word);
+            }
+            command => panic!("expected the play command, got {:?}", command),
+        }
+
+        let cli = Cli::try_parse_from(["wordle_cli", "daily", "words.txt"]).unwrap();
+        assert!(matches!(cli.into_command(), Command::Daily(_)));
+    }
 }
@@ -0,0 +1,653 @@
+use std::io::{self, stdin, BufRead, BufReader, Write};
+use std::net::{Shutdown, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use colored::Colorize;
+use serde_json::{json, Value};
+
+use crate::game::Game;
+use crate::letter::{Letter, LetterState};
+use crate::protocol::{guess_json, result_json, start_json, write_line};
+use crate::render::{state_from_name, state_name, BoxRenderer, Renderer};
+use crate::theme::Theme;
+use crate::word::Word;
+use crate::{answer_pool, fresh_word, load_words, plain_output, Errors, GameOptions};
+
+/// The address the server listens on if no other address is given
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:7777";
+
+/// Struct to represent the puzzle every player on the server is racing to solve
+#[derive(Clone, Debug)]
+pub struct Puzzle {
+    pub word_to_guess: String,
+    pub words: Vec<String>,
+    pub max_guesses: usize,
+    pub hard_mode: bool,
+}
+
+impl Puzzle {
+    /// Returns a new game of the puzzle for one player
+    fn game(&self) -> Game {
+        Game::new(
+            self.word_to_guess.clone(),
+            self.words.clone(),
+            self.max_guesses,
+            self.hard_mode,
+        )
+    }
+}
+
+/// Struct to represent a player connected to the server
+struct Player {
+    id: usize,
+    name: String,
+    sender: Sender<Value>,
+    rows: Vec<Vec<LetterState>>,
+    won: bool,
+    over: bool,
+    place: Option<usize>,
+}
+
+impl Player {
+    /// Returns the progress of the player as a JSON value. Only the states of the letters are
+    /// shared, so other players can't see the letters that were guessed
+    fn progress_json(&self) -> Value {
+        let rows: Vec<Vec<&str>> = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(state_name).collect())
+            .collect();
+
+        json!({
+            "type": "progress",
+            "player": self.name,
+            "rows": rows,
+            "won": self.won,
+            "over": self.over,
+            "place": self.place,
+        })
+    }
+}
+
+/// Struct to represent every player connected to the server. Lines for a player are queued on
+/// the lobby and written by that player's own writer thread, so a slow connection never holds the
+/// lobby locked and lines written by different threads can't be mixed together
+#[derive(Default)]
+struct Lobby {
+    players: Vec<Player>,
+    next_id: usize,
+    solved: usize,
+}
+
+impl Lobby {
+    /// Adds a player to the lobby, returning their id and the name they were given. Names are
+    /// numbered if they are already taken, so every player can be told apart
+    ///
+    /// Parameters
+    /// name:       The name the player asked for
+    /// stream:     The connection to the player, handed to a writer thread of its own
+    fn join(&mut self, name: &str, stream: TcpStream) -> (usize, String) {
+        let mut unique_name = name.to_string();
+        let mut number = 1;
+
+        while self.players.iter().any(|player| player.name == unique_name) {
+            number += 1;
+            unique_name = format!("{} ({})", name, number);
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+
+        self.players.push(Player {
+            id,
+            name: unique_name.clone(),
+            sender: spawn_writer(stream),
+            rows: Vec::new(),
+            won: false,
+            over: false,
+            place: None,
+        });
+
+        (id, unique_name)
+    }
+
+    /// Removes a player from the lobby
+    ///
+    /// Parameters
+    /// id:     The id of the player
+    fn leave(&mut self, id: usize) {
+        self.players.retain(|player| player.id != id);
+    }
+
+    /// Records the progress a player has made, returning it as a JSON value
+    ///
+    /// Parameters
+    /// id:     The id of the player
+    /// game:   The game the player is playing
+    fn update(&mut self, id: usize, game: &Game) -> Option<Value> {
+        let player = self.players.iter_mut().find(|player| player.id == id)?;
+
+        player.rows = game
+            .guesses()
+            .iter()
+            .map(|word| word.letters().iter().map(Letter::state).collect())
+            .collect();
+        player.over = game.is_over();
+
+        // Places are given out in the order players solve the puzzle
+        if game.is_won() && !player.won {
+            self.solved += 1;
+            player.won = true;
+            player.place = Some(self.solved);
+        }
+
+        Some(player.progress_json())
+    }
+
+    /// Queues a line for a single player. A player whose writer thread has stopped couldn't be
+    /// written to, and is removed from the lobby
+    ///
+    /// Parameters
+    /// id:     The id of the player
+    /// value:  The line to write
+    fn send(&mut self, id: usize, value: &Value) {
+        self.players
+            .retain(|player| player.id != id || player.sender.send(value.clone()).is_ok());
+    }
+
+    /// Queues a line for every player, removing any player that couldn't be written to
+    ///
+    /// Parameters
+    /// value:  The line to write
+    fn broadcast(&mut self, value: &Value) {
+        self.players
+            .retain(|player| player.sender.send(value.clone()).is_ok());
+    }
+}
+
+/// Starts a thread writing the lines queued for a player to their connection, returning the
+/// sender to queue lines on. If a write fails the connection is shut down, which ends the
+/// player's own thread and stops any more lines being queued for them
+///
+/// Parameters
+/// stream:     The connection to the player
+fn spawn_writer(mut stream: TcpStream) -> Sender<Value> {
+    let (sender, receiver) = mpsc::channel::<Value>();
+
+    thread::spawn(move || {
+        for value in receiver {
+            if write_line(&mut stream, &value).is_err() {
+                let _ = stream.shutdown(Shutdown::Both);
+                break;
+            }
+        }
+    });
+
+    sender
+}
+
+/// Hosts a puzzle for players to race on, accepting players until the program is stopped
+///
+/// Parameters
+/// listener:   The socket to accept players on
+/// puzzle:     The puzzle every player races to solve
+pub fn serve(listener: TcpListener, puzzle: Puzzle) {
+    let lobby = Arc::new(Mutex::new(Lobby::default()));
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(_) => continue,
+        };
+
+        let lobby = Arc::clone(&lobby);
+        let puzzle = puzzle.clone();
+
+        thread::spawn(move || {
+            // A player's connection failing only ends the game for that player
+            let _ = handle_player(stream, &puzzle, &lobby);
+        });
+    }
+}
+
+/// Plays the puzzle with a single player. The first line the player sends is their name, and
+/// every line after that is a guess, the same as the JSON line protocol. Once the player's game
+/// is over they stay connected to watch the other players finish
+///
+/// Parameters
+/// stream:     The connection to the player
+/// puzzle:     The puzzle the player is solving
+/// lobby:      Every player connected to the server
+fn handle_player(stream: TcpStream, puzzle: &Puzzle, lobby: &Mutex<Lobby>) -> io::Result<()> {
+    let mut lines = BufReader::new(stream.try_clone()?).lines();
+
+    let name = match lines.next() {
+        Some(line) => line?.trim().to_string(),
+        None => return Ok(()),
+    };
+    let name = if name.is_empty() {
+        String::from("player")
+    } else {
+        name
+    };
+
+    let mut game = puzzle.game();
+
+    // Joining and sending the first line under one lock, so the start line is always the first
+    // line the player gets
+    let (id, name) = {
+        let mut lobby = lobby.lock().unwrap();
+        let (id, name) = lobby.join(&name, stream);
+
+        let mut start = start_json(&game, false);
+        start["player"] = Value::from(name.as_str());
+        lobby.send(id, &start);
+
+        // Catching the new player up on everyone else's progress, then telling everyone else
+        // about the new player
+        let others: Vec<Value> = lobby
+            .players
+            .iter()
+            .filter(|player| player.id != id)
+            .map(Player::progress_json)
+            .collect();
+        for progress in others {
+            lobby.send(id, &progress);
+        }
+
+        if let Some(progress) = lobby.update(id, &game) {
+            lobby.broadcast(&progress);
+        }
+
+        (id, name)
+    };
+
+    println!("{} joined the race", name);
+
+    for line in lines {
+        let line = line?;
+
+        // Guesses after the game is over are ignored, the player is only watching now
+        if game.is_over() {
+            continue;
+        }
+
+        let response = guess_json(&mut game, &line);
+
+        let mut lobby = lobby.lock().unwrap();
+        lobby.send(id, &response);
+
+        if response["type"] == "guess" {
+            if let Some(progress) = lobby.update(id, &game) {
+                lobby.broadcast(&progress);
+            }
+        }
+
+        if game.is_over() {
+            lobby.send(id, &result_json(&game));
+
+            match game.is_won() {
+                true => println!("{} solved it in {} guesses", name, game.guesses().len()),
+                false => println!("{} ran out of guesses", name),
+            }
+        }
+    }
+
+    lobby.lock().unwrap().leave(id);
+    println!("{} left the race", name);
+
+    Ok(())
+}
+
+/// Chooses a word and hosts it for players to race on
+///
+/// Parameters
+/// options:    The options the puzzle is played with
+/// address:    The address to listen for players on
+pub fn host(options: GameOptions, address: &str) -> Result<(), Errors> {
+    let words = load_words(options.word_list, options.word_length)?;
+
+    let puzzle = Puzzle {
+        word_to_guess: fresh_word(&answer_pool(&words, options.difficulty))?,
+        words,
+        max_guesses: options.max_guesses,
+        hard_mode: options.hard_mode,
+    };
+
+    let listener = TcpListener::bind(address).map_err(|err| Errors::Network(err.to_string()))?;
+
+    println!("Hosting a race on {}, press Ctrl-C to stop", address);
+
+    if options.show_word {
+        println!("The word is {}", puzzle.word_to_guess.bold());
+    }
+
+    serve(listener, puzzle);
+
+    Ok(())
+}
+
+/// Joins a race hosted on another computer, sending guesses typed into stdin and printing the
+/// results along with every other player's progress
+///
+/// Parameters
+/// address:    The address of the server
+/// name:       The name to race under
+/// theme:      The theme to draw the guesses with
+pub fn join(address: impl ToSocketAddrs, name: &str, theme: Theme) -> Result<(), Errors> {
+    let network_error = |err: io::Error| Errors::Network(err.to_string());
+
+    // Without colours the blocks can only be told apart by their symbols
+    let theme = match plain_output() {
+        true => {
+            colored::control::set_override(false);
+            Theme::new(theme.palette(), true)
+        }
+        false => theme,
+    };
+
+    let stream = TcpStream::connect(address).map_err(network_error)?;
+    let mut writer = stream.try_clone().map_err(network_error)?;
+
+    writeln!(writer, "{}", name).map_err(network_error)?;
+
+    // Guesses are read on their own thread, so other players' progress can be printed while
+    // waiting for the player to type
+    thread::spawn(move || {
+        for line in stdin().lines() {
+            match line {
+                Ok(line) if writeln!(writer, "{}", line).is_ok() => (),
+                _ => break,
+            }
+        }
+    });
+
+    let mut own_name = name.to_string();
+    let mut max_guesses = 0;
+
+    for line in BufReader::new(stream).lines() {
+        let line = line.map_err(network_error)?;
+
+        let value: Value = match serde_json::from_str(&line) {
+            Ok(value) => value,
+            Err(_) => continue,
+        };
+
+        match value["type"].as_str() {
+            Some("start") => {
+                // The server may have numbered the name if it was already taken
+                if let Some(name) = value["player"].as_str() {
+                    own_name = name.to_string();
+                }
+                max_guesses = value["max_guesses"].as_u64().unwrap_or(0) as usize;
+
+                println!(
+                    "Joined the race as {}! The word is {} letters long and you get {} guesses",
+                    own_name.bold(),
+                    value["word_length"],
+                    max_guesses
+                );
+            }
+            Some("guess") => {
+                if let Some(word) = word_from_json(&value["guess"]) {
+                    for row in BoxRenderer::new(theme).render_guess(&word) {
+                        println!("{}", row);
+                    }
+                }
+            }
+            Some("error") => println!("{}", value["message"].as_str().unwrap_or("")),
+            Some("progress") if value["player"] != own_name.as_str() => {
+                println!("{}", progress_line(&value, &theme, max_guesses));
+            }
+            Some("result") => {
+                println!(
+                    "The word was {}",
+                    value["answer"].as_str().unwrap_or("").bold()
+                );
+                println!("Waiting for the other players, press Ctrl-C to leave");
+            }
+            _ => (),
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns a guess scored by the server as a Word struct
+///
+/// Parameters
+/// value:  The guess, as written by the JSON line protocol
+fn word_from_json(value: &Value) -> Option<Word> {
+    let mut letters: Vec<Letter> = Vec::new();
+
+    for letter in value["letters"].as_array()? {
+        let state = state_from_name(letter["state"].as_str()?)?;
+        let letter = letter["letter"].as_str()?.chars().next()?;
+
+        letters.push(Letter::new(letter, state));
+    }
+
+    Some(Word::from_letters(letters))
+}
+
+/// Returns a line showing another player's latest guess as coloured blocks, without its letters
+///
+/// Parameters
+/// value:          The player's progress, as written by the server
+/// theme:          The theme to colour the blocks with
+/// max_guesses:    The number of guesses each player gets
+fn progress_line(value: &Value, theme: &Theme, max_guesses: usize) -> String {
+    let name = value["player"].as_str().unwrap_or("");
+    let rows = value["rows"].as_array().cloned().unwrap_or_default();
+
+    let mut blocks = String::new();
+
+    if let Some(row) = rows.last().and_then(Value::as_array) {
+        for state in row
+            .iter()
+            .filter_map(|state| state.as_str().and_then(state_from_name))
+        {
+            // Symbols stand in for the colours when the theme asks for them
+            let block = match theme.symbols() {
+                true => format!(" {} ", Theme::symbol(&state)),
+                false => String::from("   "),
+            };
+
+            blocks.push_str(&theme.paint_background(&block, &state));
+        }
+    }
+
+    let status = match (value["won"].as_bool(), value["place"].as_u64()) {
+        (Some(true), Some(place)) => format!("solved it! ({})", ordinal(place)),
+        _ if value["over"] == true => String::from("ran out of guesses"),
+        _ if rows.is_empty() => String::from("joined the race"),
+        _ => String::new(),
+    };
+
+    format!(
+        "{:<12} {} {}/{} {}",
+        name,
+        blocks,
+        rows.len(),
+        max_guesses,
+        status
+    )
+}
+
+/// Returns a number as an ordinal, such as 1st or 2nd
+///
+/// Parameters
+/// number:     The number to convert
+fn ordinal(number: u64) -> String {
+    let suffix = match (number % 10, number % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+
+    format!("{}{}", number, suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    /// Struct to represent a test client connected to a server on loopback
+    struct TestClient {
+        lines: io::Lines<BufReader<TcpStream>>,
+        writer: TcpStream,
+    }
+
+    impl TestClient {
+        /// Connects to the server and sends the client's name
+        fn connect(address: &str, name: &str) -> TestClient {
+            let stream = TcpStream::connect(address).unwrap();
+
+            // A server that stops answering fails the test instead of hanging it
+            stream
+                .set_read_timeout(Some(Duration::from_secs(5)))
+                .unwrap();
+            let mut writer = stream.try_clone().unwrap();
+
+            writeln!(writer, "{}", name).unwrap();
+
+            TestClient {
+                lines: BufReader::new(stream).lines(),
+                writer,
+            }
+        }
+
+        /// Sends a line to the server
+        fn send(&mut self, line: &str) {
+            writeln!(self.writer, "{}", line).unwrap();
+        }
+
+        /// Returns the next line from the server with the given type, skipping any others
+        fn next_of_type(&mut self, line_type: &str) -> Value {
+            loop {
+                let line = self.lines.next().unwrap().unwrap();
+                let value: Value = serde_json::from_str(&line).unwrap();
+
+                if value["type"] == line_type {
+                    return value;
+                }
+            }
+        }
+
+        /// Returns the next progress line about the given player, skipping any others
+        fn next_progress_of(&mut self, player: &str) -> Value {
+            loop {
+                let progress = self.next_of_type("progress");
+
+                if progress["player"] == player {
+                    return progress;
+                }
+            }
+        }
+    }
+
+    /// Starts a server on a free loopback port, returning its address
+    fn start_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+
+        let puzzle = Puzzle {
+            word_to_guess: String::from("cigar"),
+            words: vec![
+                String::from("cigar"),
+                String::from("crane"),
+                String::from("moist"),
+            ],
+            max_guesses: 6,
+            hard_mode: false,
+        };
+
+        thread::spawn(move || serve(listener, puzzle));
+
+        address
+    }
+
+    #[test]
+    /// Checks if players see each other's progress without the letters that were guessed
+    fn players_see_progress_without_letters() {
+        let address = start_server();
+
+        let mut alice = TestClient::connect(&address, "alice");
+        assert!(alice.next_of_type("start")["player"] == "alice");
+
+        let mut bob = TestClient::connect(&address, "bob");
+        bob.next_of_type("start");
+
+        // Alice is told that bob joined
+        let joined = alice.next_progress_of("bob");
+        assert!(joined["rows"].as_array().unwrap().is_empty());
+
+        bob.send("crane");
+        assert!(bob.next_of_type("guess")["guess"]["word"] == "crane");
+
+        let progress = alice.next_progress_of("bob");
+        assert!(progress["rows"][0][0] == "correct");
+        assert!(!progress.to_string().contains("crane"));
+
+        bob.send("cigar");
+        assert!(bob.next_of_type("result")["won"] == true);
+        assert!(alice.next_progress_of("bob")["place"] == 1);
+    }
+
+    #[test]
+    /// Checks if players asking for the same name are given different names
+    fn duplicate_names_are_numbered() {
+        let address = start_server();
+
+        let mut first = TestClient::connect(&address, "sam");
+        assert!(first.next_of_type("start")["player"] == "sam");
+
+        let mut second = TestClient::connect(&address, "sam");
+        assert!(second.next_of_type("start")["player"] == "sam (2)");
+    }
+
+    #[test]
+    /// Checks if players whose writer thread has stopped are removed when lines are queued
+    fn unwritable_players_are_removed() {
+        let player = |id: usize, name: &str, sender: Sender<Value>| Player {
+            id,
+            name: name.to_string(),
+            sender,
+            rows: Vec::new(),
+            won: false,
+            over: false,
+            place: None,
+        };
+
+        let (alice, _alice_receiver) = mpsc::channel();
+        let (bob, bob_receiver) = mpsc::channel();
+
+        // Bob's writer thread has stopped, so nothing can be queued for him
+        drop(bob_receiver);
+
+        let mut lobby = Lobby::default();
+        lobby.players.push(player(0, "alice", alice));
+        lobby.players.push(player(1, "bob", bob));
+
+        lobby.broadcast(&json!({"type": "progress"}));
+
+        assert!(lobby.players.len() == 1);
+        assert!(lobby.players[0].name == "alice");
+    }
+
+    #[test]
+    /// Checks if ordinals use the right suffixes
+    fn ordinal_suffixes() {
+        assert!(ordinal(1) == "1st");
+        assert!(ordinal(2) == "2nd");
+        assert!(ordinal(3) == "3rd");
+        assert!(ordinal(11) == "11th");
+        assert!(ordinal(22) == "22nd");
+    }
+}
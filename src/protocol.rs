@@ -0,0 +1,198 @@
+use std::io::{self, BufRead, Write};
+
+use clap::ValueEnum;
+use serde_json::{json, Value};
+
+use crate::build_error_message;
+use crate::game::Game;
+use crate::render::JsonRenderer;
+use crate::scoring::Scoring;
+use crate::word::BuildErrors;
+
+/// Enum for the ways the game can talk to whoever is playing it
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Protocol {
+    /// Drawn in the terminal for a person to play
+    #[default]
+    Human,
+    /// One JSON object per line, for scripts and bots to play
+    Json,
+}
+
+/// Returns the name an invalid guess is given in JSON output
+///
+/// Parameters
+/// err:    The reason the guess wasn't valid
+pub fn error_code(err: &BuildErrors) -> &'static str {
+    match err {
+        BuildErrors::NonAlphabeticCharcter => "non_alphabetic_character",
+        BuildErrors::TooShortOfWord => "too_short",
+        BuildErrors::TooLongOfWord => "too_long",
+        BuildErrors::NonValidWord => "not_in_word_list",
+        BuildErrors::IgnoredHint => "ignored_hint",
+    }
+}
+
+/// Returns the first line of the JSON line protocol, which describes the game
+///
+/// Parameters
+/// game:       The game being played
+/// show_word:  Whether to include the word to guess
+pub fn start_json(game: &Game, show_word: bool) -> Value {
+    let mut start = json!({
+        "type": "start",
+        "word_length": game.word_length(),
+        "max_guesses": game.max_guesses(),
+        "scoring": game.scoring().name(),
+    });
+
+    if show_word {
+        start["answer"] = Value::from(game.word_to_guess());
+    }
+
+    start
+}
+
+/// Makes a guess, returning the line of the JSON line protocol that responds to it
+///
+/// Parameters
+/// game:   The game being played
+/// guess:  The guess the player made
+pub fn guess_json(game: &mut Game, guess: &str) -> Value {
+    let mut response = match game.guess(guess) {
+        Ok(word) => json!({
+            "type": "guess",
+            "guess": JsonRenderer::guess_json(word),
+        }),
+        Err(err) => json!({
+            "type": "error",
+            "guess": guess.trim(),
+            "error": error_code(&err),
+            "message": build_error_message(&err, game.word_length()),
+        }),
+    };
+
+    response["remaining_guesses"] = Value::from(game.remaining_guesses());
+
+    response
+}
+
+/// Returns the last line of the JSON line protocol, which gives the result of the game, with the
+/// position of the letter that was marked wrongly in each guess when the scoring lies
+///
+/// Parameters
+/// game:   The game being played
+pub fn result_json(game: &Game) -> Value {
+    let mut result = json!({
+        "type": "result",
+        "won": game.is_won(),
+        "guesses": game.guesses().len(),
+        "answer": game.word_to_guess(),
+    });
+
+    // The lies are only given away once the game is over
+    if game.scoring() == Scoring::Fibble {
+        let lies: Vec<Value> = game
+            .guesses()
+            .iter()
+            .map(|word| Value::from(word.lie()))
+            .collect();
+
+        result["lies"] = Value::from(lies);
+    }
+
+    result
+}
+
+/// Plays the game over the JSON line protocol. Each line read from the input is one guess, and
+/// every response is written as a single JSON object on its own line. The first line written
+/// describes the game, and the last line gives the result
+///
+/// Parameters
+/// game:       The game to play
+/// input:      Where to read guesses from
+/// output:     Where to write responses to
+/// show_word:  Whether to include the word to guess in the first line
+pub fn play_json(
+    game: &mut Game,
+    input: impl BufRead,
+    mut output: impl Write,
+    show_word: bool,
+) -> io::Result<()> {
+    write_line(&mut output, &start_json(game, show_word))?;
+
+    let mut lines = input.lines();
+
+    while !game.is_over() {
+        // If the input has ended there are no more guesses to read
+        let guess = match lines.next() {
+            Some(line) => line?,
+            None => break,
+        };
+
+        write_line(&mut output, &guess_json(game, &guess))?;
+    }
+
+    write_line(&mut output, &result_json(game))
+}
+
+/// Writes a JSON value as a single line, flushing it so the reader gets it straight away
+///
+/// Parameters
+/// output:     Where to write the line
+/// value:      The value to write
+pub fn write_line(output: &mut impl Write, value: &Value) -> io::Result<()> {
+    writeln!(output, "{}", value)?;
+    output.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plays a game of guessing "cigar" with the given input, returning every line written
+    fn play_lines(input: &str) -> Vec<Value> {
+        let words = vec![
+            String::from("cigar"),
+            String::from("crane"),
+            String::from("moist"),
+        ];
+        let mut game = Game::new(String::from("cigar"), words, 6, false);
+        let mut output: Vec<u8> = Vec::new();
+
+        play_json(&mut game, input.as_bytes(), &mut output, false).unwrap();
+
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    /// Checks if every guess gets one line, with errors for invalid guesses and a result at the end
+    fn play_json_writes_a_line_per_guess() {
+        let lines = play_lines("crane\nzzzzz\nCIGAR\n");
+
+        assert!(lines.len() == 5);
+        assert!(lines[0]["type"] == "start");
+        assert!(lines[0].get("answer").is_none());
+        assert!(lines[1]["guess"]["letters"][0]["state"] == "correct");
+        assert!(lines[1]["remaining_guesses"] == 5);
+        assert!(lines[2]["error"] == "not_in_word_list");
+        assert!(lines[2]["remaining_guesses"] == 5);
+        assert!(lines[4]["type"] == "result");
+        assert!(lines[4]["won"] == true);
+        assert!(lines[4]["guesses"] == 2);
+    }
+
+    #[test]
+    /// Checks if a result is still written when the input ends before the game is over
+    fn play_json_finishes_when_input_ends() {
+        let lines = play_lines("crane\n");
+
+        assert!(lines.last().unwrap()["type"] == "result");
+        assert!(lines.last().unwrap()["won"] == false);
+        assert!(lines.last().unwrap()["answer"] == "cigar");
+    }
+}
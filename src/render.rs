@@ -0,0 +1,381 @@
+use serde_json::{json, Value};
+
+use crate::animation::{self, Effect};
+use crate::game::Game;
+use crate::letter::LetterState;
+use crate::scoring::Scoring;
+use crate::theme::Theme;
+use crate::word::Word;
+
+/// Trait for the ways a game can be drawn. Renderers only turn the state of a game into lines of
+/// text, leaving it to the frontend to decide where those lines are written
+pub trait Renderer {
+    /// Returns the lines that draw a single guess
+    ///
+    /// Parameters
+    /// word:   The guess to draw
+    fn render_guess(&self, word: &Word) -> Vec<String>;
+
+    /// Returns the lines that draw a guess the player hasn't made yet. Renderers that only draw
+    /// the guesses that have been made return no lines
+    ///
+    /// Parameters
+    /// word_length:    The length of the word to guess
+    fn render_blank(&self, _word_length: usize) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Returns the lines that draw a guess part way through an animation. Renderers that can't
+    /// be animated draw the guess as normal
+    ///
+    /// Parameters
+    /// word:       The guess to draw
+    /// _effect:    The frame of the animation to draw
+    fn render_effect(&self, word: &Word, _effect: &Effect) -> Vec<String> {
+        self.render_guess(word)
+    }
+
+    /// Returns the lines that draw the whole game
+    ///
+    /// Parameters
+    /// game:       The game to draw
+    /// effect:     The frame of an animation playing on the latest guess, if there is one
+    fn render(&self, game: &Game, effect: Option<&Effect>) -> Vec<String> {
+        let mut lines: Vec<String> = Vec::new();
+        let guesses = game.guesses();
+
+        for (i, word) in guesses.iter().enumerate() {
+            match effect {
+                Some(effect) if i == guesses.len() - 1 => {
+                    lines.extend(self.render_effect(word, effect))
+                }
+                _ => lines.extend(self.render_guess(word)),
+            }
+        }
+
+        for _i in 0..game.remaining_guesses() {
+            lines.extend(self.render_blank(game.word_length()));
+        }
+
+        lines
+    }
+}
+
+/// Struct to represent drawing each letter in a box three lines tall, which is how the game is
+/// normally drawn
+pub struct BoxRenderer {
+    theme: Theme,
+}
+
+impl BoxRenderer {
+    /// Returns a BoxRenderer struct
+    ///
+    /// Parameters
+    /// theme:    The theme to style the letters with
+    pub fn new(theme: Theme) -> BoxRenderer {
+        BoxRenderer { theme }
+    }
+}
+
+impl Renderer for BoxRenderer {
+    fn render_guess(&self, word: &Word) -> Vec<String> {
+        word.rows(&self.theme)
+    }
+
+    fn render_blank(&self, word_length: usize) -> Vec<String> {
+        vec![
+            "╭───╮".repeat(word_length),
+            "│   │".repeat(word_length),
+            "╰───╯".repeat(word_length),
+        ]
+    }
+
+    fn render_effect(&self, word: &Word, effect: &Effect) -> Vec<String> {
+        animation::animated_rows(word, &self.theme, effect)
+    }
+}
+
+/// Struct to represent drawing each guess on one line of coloured letters, for small terminals
+pub struct CompactRenderer {
+    theme: Theme,
+}
+
+impl CompactRenderer {
+    /// Returns a CompactRenderer struct
+    ///
+    /// Parameters
+    /// theme:    The theme to style the letters with
+    pub fn new(theme: Theme) -> CompactRenderer {
+        CompactRenderer { theme }
+    }
+}
+
+impl Renderer for CompactRenderer {
+    fn render_guess(&self, word: &Word) -> Vec<String> {
+        vec![word.compact_row(&self.theme)]
+    }
+
+    fn render_blank(&self, word_length: usize) -> Vec<String> {
+        vec![" · ".repeat(word_length)]
+    }
+
+    fn render_effect(&self, word: &Word, effect: &Effect) -> Vec<String> {
+        vec![animation::animated_compact_row(word, &self.theme, effect)]
+    }
+}
+
+/// Struct to represent drawing each guess as a line of plain text, for output that can't show
+/// colours or move the cursor
+pub struct PlainRenderer;
+
+impl Renderer for PlainRenderer {
+    fn render_guess(&self, word: &Word) -> Vec<String> {
+        vec![word.plain()]
+    }
+}
+
+/// Struct to represent drawing the game as a JSON object, for other programs to read
+pub struct JsonRenderer;
+
+impl JsonRenderer {
+    /// Returns a guess as a JSON value, with the state of each of its letters. Guesses scored by
+    /// their pegs leave the states out and give the pegs instead
+    ///
+    /// Parameters
+    /// word:   The guess to convert
+    pub fn guess_json(word: &Word) -> Value {
+        let letters: Vec<Value> = word
+            .letters()
+            .iter()
+            .map(|letter| match word.scoring() {
+                Scoring::Letters | Scoring::Fibble => json!({
+                    "letter": letter.letter().to_string(),
+                    "state": state_name(&letter.state()),
+                }),
+                Scoring::Mastermind => json!({ "letter": letter.letter().to_string() }),
+            })
+            .collect();
+
+        let mut guess = json!({
+            "word": word.to_string(),
+            "letters": letters,
+        });
+
+        if word.scoring() == Scoring::Mastermind {
+            let pegs = word.pegs();
+
+            guess["in_place"] = Value::from(pegs.in_place);
+            guess["elsewhere"] = Value::from(pegs.elsewhere);
+        }
+
+        guess
+    }
+
+    /// Returns the whole game as a JSON value
+    ///
+    /// Parameters
+    /// game:   The game to convert
+    pub fn game_json(game: &Game) -> Value {
+        let guesses: Vec<Value> = game
+            .guesses()
+            .iter()
+            .map(JsonRenderer::guess_json)
+            .collect();
+
+        json!({
+            "guesses": guesses,
+            "remaining_guesses": game.remaining_guesses(),
+            "won": game.is_won(),
+            "over": game.is_over(),
+        })
+    }
+}
+
+impl Renderer for JsonRenderer {
+    fn render_guess(&self, word: &Word) -> Vec<String> {
+        vec![JsonRenderer::guess_json(word).to_string()]
+    }
+
+    // The whole game is one JSON object, rather than an object for each guess
+    fn render(&self, game: &Game, _effect: Option<&Effect>) -> Vec<String> {
+        vec![JsonRenderer::game_json(game).to_string()]
+    }
+}
+
+/// Returns the name a letter state is given in JSON output
+///
+/// Parameters
+/// letter_state:   The state to name
+pub fn state_name(letter_state: &LetterState) -> &'static str {
+    match letter_state {
+        LetterState::RightLetterRightPlace => "correct",
+        LetterState::RightLetterWrongPlace => "present",
+        LetterState::WrongLetterWrongPlace => "absent",
+    }
+}
+
+/// Returns the letter state a name from JSON output stands for
+///
+/// Parameters
+/// name:   The name of the state
+pub fn state_from_name(name: &str) -> Option<LetterState> {
+    match name {
+        "correct" => Some(LetterState::RightLetterRightPlace),
+        "present" => Some(LetterState::RightLetterWrongPlace),
+        "absent" => Some(LetterState::WrongLetterWrongPlace),
+        _ => None,
+    }
+}
+
+/// Returns the renderer for drawing the grid in a terminal
+///
+/// Parameters
+/// theme:      The theme to style the letters with
+/// compact:    Whether to draw each guess on one line instead of in boxes
+pub fn grid_renderer(theme: Theme, compact: bool) -> Box<dyn Renderer> {
+    if compact {
+        Box::new(CompactRenderer::new(theme))
+    } else {
+        Box::new(BoxRenderer::new(theme))
+    }
+}
+
+/// Returns the number of columns a line takes up on screen, skipping over any colour codes
+///
+/// Parameters
+/// line:   The line to measure
+pub fn visible_width(line: &str) -> usize {
+    let mut width = 0;
+    let mut in_escape = false;
+
+    for letter in line.chars() {
+        match (in_escape, letter) {
+            (false, '\x1b') => in_escape = true,
+            (false, _) => width += 1,
+            // Colour codes end with a letter, such as the 'm' in "\x1b[32m"
+            (true, letter) if letter.is_ascii_alphabetic() => in_escape = false,
+            (true, _) => (),
+        }
+    }
+
+    width
+}
+
+/// Returns boards drawn next to each other, with each board padded to the width of its widest
+/// line so the boards after it line up
+///
+/// Parameters
+/// boards:     The lines of each board, from left to right
+/// gap:        The number of columns between boards
+pub fn side_by_side(boards: &[Vec<String>], gap: usize) -> Vec<String> {
+    let height = boards.iter().map(Vec::len).max().unwrap_or(0);
+    let mut lines = vec![String::new(); height];
+
+    for (i, board) in boards.iter().enumerate() {
+        let width = board
+            .iter()
+            .map(|line| visible_width(line))
+            .max()
+            .unwrap_or(0);
+
+        for (row, line) in lines.iter_mut().enumerate() {
+            let board_line = board.get(row).map(String::as_str).unwrap_or("");
+
+            line.push_str(board_line);
+
+            // The last board doesn't need padding, as nothing is drawn after it
+            if i < boards.len() - 1 {
+                let padding = width - visible_width(board_line) + gap;
+                line.push_str(&" ".repeat(padding));
+            }
+        }
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a game of guessing "cigar" with one guess made
+    fn test_game() -> Game {
+        let words = vec![String::from("cigar"), String::from("crane")];
+        let mut game = Game::new(String::from("cigar"), words, 6, false);
+
+        game.guess("crane").unwrap();
+
+        game
+    }
+
+    #[test]
+    /// Checks if the box renderer fills the rest of the grid with blank boxes
+    fn box_renderer_draws_full_grid() {
+        let lines = BoxRenderer::new(Theme::default()).render(&test_game(), None);
+
+        assert!(lines.len() == 18);
+        assert!(lines[17] == "╰───╯╰───╯╰───╯╰───╯╰───╯");
+    }
+
+    #[test]
+    /// Checks if the plain renderer only draws the guesses that have been made
+    fn plain_renderer_draws_guesses() {
+        let lines = PlainRenderer.render(&test_game(), None);
+
+        assert!(lines == vec![String::from("[C](R)(A)ne")]);
+    }
+
+    #[test]
+    /// Checks if the JSON renderer describes the state of the game and each letter
+    fn json_renderer_draws_state() {
+        let lines = JsonRenderer.render(&test_game(), None);
+        let value: Value = serde_json::from_str(&lines[0]).unwrap();
+
+        assert!(value["remaining_guesses"] == 5);
+        assert!(value["won"] == false);
+        assert!(value["guesses"][0]["word"] == "crane");
+        assert!(value["guesses"][0]["letters"][0]["state"] == "correct");
+        assert!(value["guesses"][0]["letters"][1]["state"] == "present");
+        assert!(value["guesses"][0]["letters"][4]["state"] == "absent");
+    }
+
+    #[test]
+    /// Checks if guesses scored by their pegs don't give away which letters are right
+    fn mastermind_guesses_hide_letter_states() {
+        let words = vec![String::from("cigar"), String::from("crane")];
+        let mut game =
+            Game::new(String::from("cigar"), words, 6, false).with_scoring(Scoring::Mastermind);
+        game.guess("crane").unwrap();
+
+        let rows = BoxRenderer::new(Theme::default()).render_guess(&game.guesses()[0]);
+        assert!(rows[1] == "│ C ││ R ││ A ││ N ││ E │  ●○○··");
+        assert!(rows
+            .iter()
+            .all(|row| visible_width(row) == visible_width(&rows[1])));
+
+        let lines = PlainRenderer.render(&game, None);
+        assert!(lines == vec![String::from("CRANE  1 in place, 2 elsewhere")]);
+
+        let value = JsonRenderer::guess_json(&game.guesses()[0]);
+        assert!(value["in_place"] == 1 && value["elsewhere"] == 2);
+        assert!(value["letters"][0].get("state").is_none());
+    }
+
+    #[test]
+    /// Checks if colour codes aren't counted towards the width of a line
+    fn visible_width_skips_colour_codes() {
+        assert!(visible_width("\x1b[92m╭───╮\x1b[0m") == 5);
+        assert!(visible_width("> CRA__") == 7);
+    }
+
+    #[test]
+    /// Checks if boards of different widths are lined up next to each other
+    fn side_by_side_pads_boards() {
+        let boards = vec![
+            vec![String::from("ab"), String::from("a")],
+            vec![String::from("cd"), String::from("c")],
+        ];
+
+        assert!(side_by_side(&boards, 2) == vec!["ab  cd", "a   c"]);
+    }
+}
@@ -0,0 +1,140 @@
+use std::fmt;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::letter::{Letter, LetterState};
+use crate::solver;
+
+/// Enum for the rules a guess is scored by
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Scoring {
+    /// Every letter is marked as in the right place, in the wrong place or not in the word
+    #[default]
+    Letters,
+    /// Only the number of letters in the right place and in the wrong place is shown, like the
+    /// pegs in Mastermind, without saying which letters they are
+    Mastermind,
+    /// Every letter is marked, but one letter in each guess is marked wrongly on purpose
+    Fibble,
+}
+
+impl Scoring {
+    /// Returns the name the rules are given in settings and JSON output
+    pub fn name(&self) -> &'static str {
+        match self {
+            Scoring::Letters => "letters",
+            Scoring::Mastermind => "mastermind",
+            Scoring::Fibble => "fibble",
+        }
+    }
+}
+
+/// Struct to represent the feedback a guess gets when it is scored like Mastermind
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Pegs {
+    /// The number of letters in the right place
+    pub in_place: usize,
+    /// The number of letters in the word, but in the wrong place
+    pub elsewhere: usize,
+}
+
+impl Pegs {
+    /// Returns the pegs for letters that have already been scored
+    ///
+    /// Parameters
+    /// letters:    The scored letters of a guess
+    pub fn from_letters(letters: &[Letter]) -> Pegs {
+        let count = |state: LetterState| {
+            letters
+                .iter()
+                .filter(|letter| letter.state() == state)
+                .count()
+        };
+
+        Pegs {
+            in_place: count(LetterState::RightLetterRightPlace),
+            elsewhere: count(LetterState::RightLetterWrongPlace),
+        }
+    }
+
+    /// Returns the pegs a guess would get if a word was the answer
+    ///
+    /// Parameters
+    /// guess:  The guess, already case folded
+    /// answer: The word to score the guess against, already case folded
+    pub fn between(guess: &str, answer: &str) -> Pegs {
+        let mut feedback = solver::feedback(guess, answer);
+        let mut pegs = Pegs::default();
+
+        // The feedback has one base 3 digit for each letter, with 2 for the right place and 1 for
+        // the wrong place
+        while feedback > 0 {
+            match feedback % 3 {
+                2 => pegs.in_place += 1,
+                1 => pegs.elsewhere += 1,
+                _ => (),
+            }
+
+            feedback /= 3;
+        }
+
+        pegs
+    }
+
+    /// Returns the pegs as a row of symbols, a filled circle for each letter in the right place
+    /// and a hollow one for each letter in the wrong place, padded with dots to the word length
+    ///
+    /// Parameters
+    /// word_length:    The length of the word the pegs are for
+    pub fn symbols(&self, word_length: usize) -> String {
+        let missing = word_length.saturating_sub(self.in_place + self.elsewhere);
+
+        format!(
+            "{}{}{}",
+            "●".repeat(self.in_place),
+            "○".repeat(self.elsewhere),
+            "·".repeat(missing)
+        )
+    }
+}
+
+impl fmt::Display for Pegs {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} in place, {} elsewhere",
+            self.in_place, self.elsewhere
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Checks if the pegs count letters the same way as letter scoring, including repeated letters
+    fn pegs_count_scored_letters() {
+        assert!(
+            Pegs::between("crane", "cigar")
+                == Pegs {
+                    in_place: 1,
+                    elsewhere: 2
+                }
+        );
+
+        // Only one of the b's in "abbey" is in "cabin"
+        assert!(
+            Pegs::between("abbey", "cabin")
+                == Pegs {
+                    in_place: 1,
+                    elsewhere: 1
+                }
+        );
+
+        assert!(Pegs::between("cigar", "cigar").in_place == 5);
+        assert!(Pegs::between("crane", "cigar").symbols(5) == "●○○··");
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+use crate::{random_word, Errors};
+
+/// The name of the file the served answers are kept in, in the data directory
+pub const SERVED_FILE: &str = "served.json";
+
+/// Struct to represent the answers that have already been served, so they aren't served again
+/// until every other word has been
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Served {
+    /// The answers served so far
+    pub words: BTreeSet<String>,
+}
+
+impl Served {
+    /// Reads the served answers from a file. A file that doesn't exist is treated as no answers
+    /// served yet
+    ///
+    /// Parameters
+    /// path:   The path of the served answers file
+    pub fn load(path: &Path) -> Result<Served, Errors> {
+        match fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map_err(|err| Errors::InvalidServed(err.to_string())),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Served::default()),
+            Err(err) => Err(Errors::InvalidServed(err.to_string())),
+        }
+    }
+
+    /// Writes the served answers to a file, creating the directory it is in if needed
+    ///
+    /// Parameters
+    /// path:   The path of the served answers file
+    pub fn save(&self, path: &Path) -> Result<(), Errors> {
+        let to_error = |err: std::io::Error| Errors::InvalidServed(err.to_string());
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(to_error)?;
+        }
+
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|err| Errors::InvalidServed(err.to_string()))?;
+
+        fs::write(path, contents).map_err(to_error)
+    }
+
+    /// Returns a random answer that hasn't been served yet, marking it as served. Once every word
+    /// the answer can be drawn from has been served, those words are forgotten so they can be
+    /// served again
+    ///
+    /// Parameters
+    /// pool:   The words the answer can be drawn from, which can't be empty
+    pub fn pick(&mut self, pool: &[String]) -> String {
+        let mut fresh: Vec<String> = pool
+            .iter()
+            .filter(|word| !self.words.contains(*word))
+            .cloned()
+            .collect();
+
+        if fresh.is_empty() {
+            for word in pool {
+                self.words.remove(word);
+            }
+
+            fresh = pool.to_vec();
+        }
+
+        let word = random_word(&fresh);
+        self.words.insert(word.clone());
+
+        word
+    }
+}
+
+/// Loads the served answers, lets them be changed, then saves them again. When there is nowhere
+/// to keep them they are changed without being saved, and a file that can't be read is started
+/// again from no answers served, so a broken file can't stop games from being played
+///
+/// Parameters
+/// change:     The change to make to the served answers
+pub fn update<T>(change: impl FnOnce(&mut Served) -> T) -> Result<T, Errors> {
+    let path = match config::data_path(SERVED_FILE) {
+        Some(path) => path,
+        None => return Ok(change(&mut Served::default())),
+    };
+
+    let mut served = Served::load(&path).unwrap_or_default();
+    let result = change(&mut served);
+    served.save(&path)?;
+
+    Ok(result)
+}
+
+/// Forgets every answer served so far, without reading the file first so a broken file can still
+/// be reset
+pub fn reset() -> Result<(), Errors> {
+    match config::data_path(SERVED_FILE) {
+        Some(path) => Served::default().save(&path),
+        None => Ok(()),
+    }
+}
+
+/// Returns the served answers, or none if there is nowhere to keep them
+pub fn load() -> Result<Served, Errors> {
+    match config::data_path(SERVED_FILE) {
+        Some(path) => Served::load(&path),
+        None => Ok(Served::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Checks if no answer is served twice until every word has been served
+    fn pick_skips_served_answers() {
+        let pool: Vec<String> = ["cigar", "crane", "moist"]
+            .iter()
+            .map(|word| word.to_string())
+            .collect();
+
+        let mut served = Served::default();
+        let mut picked: Vec<String> = (0..3).map(|_| served.pick(&pool)).collect();
+        picked.sort();
+
+        assert!(picked == vec!["cigar", "crane", "moist"]);
+
+        // With every word served the pool starts again, forgetting the earlier answers
+        let next = served.pick(&pool);
+        assert!(served.words.len() == 1);
+        assert!(served.words.contains(&next));
+    }
+
+    #[test]
+    /// Checks if a broken served answers file is reported, and an empty one can be saved over it
+    fn broken_file_can_be_replaced() {
+        let path =
+            std::env::temp_dir().join(format!("wordle_cli_served_{}.json", std::process::id()));
+
+        fs::write(&path, "not json").unwrap();
+        assert!(Served::load(&path).is_err());
+
+        Served::default().save(&path).unwrap();
+        assert!(Served::load(&path).unwrap() == Served::default());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,460 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::letter::LetterState;
+use crate::scoring::{Pegs, Scoring};
+use crate::word::Word;
+
+/// The number of words, taken from those with the most common letters, that the solver rates
+/// when working out how many guesses each word takes
+const GUESS_SHORTLIST: usize = 100;
+
+/// Returns the feedback a guess would get if a word were the answer, packed into a number with
+/// one base 3 digit per letter. This scores the same way as `Word::new`, but without building the
+/// letters, as the solver scores every word against every other word
+///
+/// Parameters
+/// guess:  The guess to score, already case folded
+/// answer: The word the guess is scored against, already case folded
+pub fn feedback(guess: &str, answer: &str) -> u64 {
+    let guess: Vec<char> = guess.chars().collect();
+    let answer: Vec<char> = answer.chars().collect();
+
+    feedback_chars(&guess, &answer, &mut Vec::new())
+}
+
+/// Returns the packed feedback for a guess and answer that have already been split into letters
+///
+/// Parameters
+/// guess:  The letters of the guess
+/// answer: The letters of the word the guess is scored against
+/// taken:  Space to mark the letters of the answer that have been matched, reused between calls
+fn feedback_chars(guess: &[char], answer: &[char], taken: &mut Vec<bool>) -> u64 {
+    taken.clear();
+    taken.resize(answer.len(), false);
+
+    // Right place letters are taken out first, so they can't be counted again as a wrong place
+    for (i, letter) in guess.iter().enumerate() {
+        if answer.get(i) == Some(letter) {
+            taken[i] = true;
+        }
+    }
+
+    guess
+        .iter()
+        .enumerate()
+        .fold(0, |packed: u64, (i, letter)| {
+            let digit = if answer.get(i) == Some(letter) {
+                2
+            } else {
+                match (0..answer.len()).find(|&j| !taken[j] && answer[j] == *letter) {
+                    Some(j) => {
+                        taken[j] = true;
+                        1
+                    }
+                    None => 0,
+                }
+            };
+
+            packed.wrapping_mul(3) + digit
+        })
+}
+
+/// Returns the feedback a scored guess got, packed the same way as `feedback`
+///
+/// Parameters
+/// word:   The scored guess
+fn word_feedback(word: &Word) -> u64 {
+    pack(word.letters().iter().map(|letter| letter.state()))
+}
+
+/// Packs letter states into a number with one base 3 digit per letter
+///
+/// Parameters
+/// states: The states of each letter, in order
+fn pack(states: impl IntoIterator<Item = LetterState>) -> u64 {
+    states.into_iter().fold(0, |packed, state| {
+        let digit = match state {
+            LetterState::WrongLetterWrongPlace => 0,
+            LetterState::RightLetterWrongPlace => 1,
+            LetterState::RightLetterRightPlace => 2,
+        };
+
+        packed * 3 + digit
+    })
+}
+
+/// Returns whether a word could still be the answer given the feedback a guess got
+///
+/// Parameters
+/// candidate:  The word to check, already case folded
+/// guess:      The scored guess
+pub fn is_consistent(candidate: &str, guess: &Word) -> bool {
+    match guess.scoring() {
+        Scoring::Letters => feedback(&guess.to_string(), candidate) == word_feedback(guess),
+        Scoring::Mastermind => Pegs::between(&guess.to_string(), candidate) == guess.pegs(),
+        // Exactly one letter was marked wrongly, so exactly one letter has to differ
+        Scoring::Fibble => {
+            let length = guess.letters().len();
+            differing_letters(
+                feedback(&guess.to_string(), candidate),
+                word_feedback(guess),
+                length,
+            ) == 1
+        }
+    }
+}
+
+/// Returns the number of letters two packed feedbacks mark differently
+///
+/// Parameters
+/// a:      The first feedback
+/// b:      The second feedback
+/// length: The number of letters the feedback is for
+fn differing_letters(mut a: u64, mut b: u64, length: usize) -> usize {
+    let mut differing = 0;
+
+    for _ in 0..length {
+        if a % 3 != b % 3 {
+            differing += 1;
+        }
+
+        a /= 3;
+        b /= 3;
+    }
+
+    differing
+}
+
+/// Returns the words that could still be the answer given the feedback every guess got
+///
+/// Parameters
+/// words:      The words the answer could be before any guesses
+/// guesses:    The scored guesses made so far
+pub fn remaining(words: &[String], guesses: &[Word]) -> Vec<String> {
+    words
+        .iter()
+        .filter(|word| guesses.iter().all(|guess| is_consistent(word, guess)))
+        .cloned()
+        .collect()
+}
+
+/// Returns the information a guess is expected to give, in bits, which is the entropy of the
+/// feedback it would get across every word that could still be the answer
+///
+/// Parameters
+/// guess:      The guess to rate, already case folded
+/// candidates: The words that could still be the answer
+pub fn expected_bits(guess: &str, candidates: &[String]) -> f64 {
+    let guess: Vec<char> = guess.chars().collect();
+
+    expected_bits_chars(&guess, &split(candidates), &mut Vec::new())
+}
+
+/// Returns the expected information of a guess where every word has already been split into
+/// letters
+///
+/// Parameters
+/// guess:      The letters of the guess
+/// candidates: The letters of each word that could still be the answer
+/// patterns:   Space for the feedback against each candidate, reused between calls
+fn expected_bits_chars(guess: &[char], candidates: &[Vec<char>], patterns: &mut Vec<u64>) -> f64 {
+    let mut taken = Vec::new();
+
+    patterns.clear();
+    patterns.extend(
+        candidates
+            .iter()
+            .map(|candidate| feedback_chars(guess, candidate, &mut taken)),
+    );
+
+    // Sorting puts equal feedback next to each other, so each group can be counted in one pass
+    patterns.sort_unstable();
+
+    let total = candidates.len() as f64;
+
+    patterns
+        .chunk_by(|a, b| a == b)
+        .map(|group| {
+            let probability = group.len() as f64 / total;
+            -probability * probability.log2()
+        })
+        .sum()
+}
+
+/// Returns each word split into its letters
+///
+/// Parameters
+/// words:  The words to split
+fn split(words: &[String]) -> Vec<Vec<char>> {
+    words.iter().map(|word| word.chars().collect()).collect()
+}
+
+/// Returns the information gained by narrowing the possible answers down, in bits
+///
+/// Parameters
+/// before: The number of words that could be the answer before
+/// after:  The number of words that could be the answer after
+pub fn bits_gained(before: usize, after: usize) -> f64 {
+    match (before, after) {
+        (0, _) | (_, 0) => 0.0,
+        (before, after) => (before as f64 / after as f64).log2(),
+    }
+}
+
+/// Returns the number of times each letter is used across some words
+///
+/// Parameters
+/// words:  The words to count the letters of
+pub fn letter_counts(words: &[String]) -> HashMap<char, usize> {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+
+    for letter in words.iter().flat_map(|word| word.chars()) {
+        *counts.entry(letter).or_insert(0) += 1;
+    }
+
+    counts
+}
+
+/// Returns how common the letters of a word are, as a percentage of every letter counted. Each
+/// letter only counts once, as a repeated letter reveals nothing new
+///
+/// Parameters
+/// word:       The word to score
+/// letters:    The number of times each letter is used
+pub fn frequency_score(word: &str, letters: &HashMap<char, usize>) -> f64 {
+    let total: usize = letters.values().sum();
+    let mut seen: Vec<char> = Vec::new();
+
+    for letter in word.chars() {
+        if !seen.contains(&letter) {
+            seen.push(letter);
+        }
+    }
+
+    match total {
+        0 => 0.0,
+        total => seen
+            .iter()
+            .map(|letter| letters.get(letter).copied().unwrap_or(0) as f64 * 100.0 / total as f64)
+            .sum(),
+    }
+}
+
+/// Returns the guess the solver would play next, which is the word expected to give the most
+/// information. Words that could still be the answer win ties, as they might also be right. With
+/// only one or two words left the solver just guesses one of them
+///
+/// Parameters
+/// words:      The words that can be guessed
+/// candidates: The words that could still be the answer
+pub fn best_guess(words: &[String], candidates: &[String]) -> Option<String> {
+    if candidates.len() <= 2 {
+        return candidates.first().cloned();
+    }
+
+    let candidate_letters = split(candidates);
+    let candidate_set: HashSet<&String> = candidates.iter().collect();
+    let mut patterns = Vec::new();
+    let mut best: Option<(&String, f64, bool)> = None;
+
+    for word in words {
+        let letters: Vec<char> = word.chars().collect();
+        let bits = expected_bits_chars(&letters, &candidate_letters, &mut patterns);
+        let is_candidate = candidate_set.contains(word);
+
+        let better = match best {
+            None => true,
+            Some((_, best_bits, best_is_candidate)) => {
+                bits > best_bits + f64::EPSILON
+                    || ((bits - best_bits).abs() <= f64::EPSILON
+                        && is_candidate
+                        && !best_is_candidate)
+            }
+        };
+
+        if better {
+            best = Some((word, bits, is_candidate));
+        }
+    }
+
+    best.map(|(word, _, _)| word.clone())
+}
+
+/// Returns the guess the solver suggests next, rating only a shortlist of the words so it is quick
+/// enough to run after every guess, even on a long word list. The shortlist is the words that use
+/// the most common letters of the words still possible, along with those words themselves when
+/// there are few enough of them
+///
+/// Parameters
+/// words:      The words that can be guessed
+/// candidates: The words that could still be the answer
+pub fn suggest(words: &[String], candidates: &[String]) -> Option<String> {
+    let mut guesses = shortlist(words, candidates);
+
+    if candidates.len() <= GUESS_SHORTLIST {
+        for candidate in candidates {
+            if !guesses.contains(candidate) {
+                guesses.push(candidate.clone());
+            }
+        }
+    }
+
+    best_guess(&guesses, candidates)
+}
+
+/// Returns the words that use the most common letters of the words still possible, which are the
+/// ones worth rating as guesses when there are too many words to rate them all
+///
+/// Parameters
+/// words:      The words to choose from
+/// candidates: The words that could still be the answer
+fn shortlist(words: &[String], candidates: &[String]) -> Vec<String> {
+    let letters = letter_counts(candidates);
+
+    let mut scored: Vec<(f64, &String)> = words
+        .iter()
+        .map(|word| (frequency_score(word, &letters), word))
+        .collect();
+
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    scored.truncate(GUESS_SHORTLIST);
+
+    scored.into_iter().map(|(_, word)| word.clone()).collect()
+}
+
+/// Returns the number of guesses the solver takes to find each word in a list. To keep this quick
+/// enough to run before a game, the solver only plays words that could still be the answer, and
+/// only rates the ones with the most common letters when there are a lot of them
+///
+/// Parameters
+/// words:  The words the answer could be
+pub fn guess_counts(words: &[String]) -> HashMap<String, usize> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    let mut unsolved: Vec<(Vec<String>, usize)> = vec![(words.to_vec(), 1)];
+
+    while let Some((candidates, guesses)) = unsolved.pop() {
+        let guess = match best_guess(&shortlist(&candidates, &candidates), &candidates) {
+            Some(guess) => guess,
+            None => continue,
+        };
+
+        // The words left after the guess are grouped by the feedback the guess would get, as
+        // each group is what the solver would be left with next
+        let mut groups: HashMap<u64, Vec<String>> = HashMap::new();
+
+        for candidate in candidates {
+            match candidate == guess {
+                true => {
+                    counts.insert(candidate, guesses);
+                }
+                false => groups
+                    .entry(feedback(&guess, &candidate))
+                    .or_default()
+                    .push(candidate),
+            }
+        }
+
+        unsolved.extend(groups.into_values().map(|group| (group, guesses + 1)));
+    }
+
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a small word list to solve with
+    fn test_words() -> Vec<String> {
+        ["cigar", "crane", "moist", "clear", "abbey", "cider"]
+            .iter()
+            .map(|word| word.to_string())
+            .collect()
+    }
+
+    #[test]
+    /// Checks if the packed feedback agrees with the colours the game gives, including repeated
+    /// letters
+    fn feedback_matches_word_scoring() {
+        let words = test_words();
+
+        for guess in &words {
+            for answer in &words {
+                let word = Word::new(guess.clone(), answer, &words).unwrap();
+
+                assert!(feedback(guess, answer) == word_feedback(&word));
+            }
+        }
+
+        // "cabin" only has one b, which is taken by the b in the right place
+        assert!(feedback("abbey", "cabin") == pack([1, 0, 2, 0, 0].map(state)));
+    }
+
+    /// Returns the letter state for a base 3 digit
+    fn state(digit: u8) -> LetterState {
+        match digit {
+            2 => LetterState::RightLetterRightPlace,
+            1 => LetterState::RightLetterWrongPlace,
+            _ => LetterState::WrongLetterWrongPlace,
+        }
+    }
+
+    #[test]
+    /// Checks if only the words that fit every guess are left, always including the answer
+    fn remaining_keeps_consistent_words() {
+        let words = test_words();
+        let guess = Word::new(String::from("crane"), "cigar", &words).unwrap();
+
+        let left = remaining(&words, &[guess]);
+
+        assert!(left.contains(&String::from("cigar")));
+        assert!(!left.contains(&String::from("crane")));
+        assert!(!left.contains(&String::from("moist")));
+    }
+
+    #[test]
+    /// Checks if the solver picks a guess that tells the remaining words apart
+    fn best_guess_splits_candidates() {
+        let words = test_words();
+
+        let guess = best_guess(&words, &words).unwrap();
+        assert!(expected_bits(&guess, &words) >= expected_bits("abbey", &words));
+
+        assert!(best_guess(&words, &words[..1]) == Some(String::from("cigar")));
+        assert!(bits_gained(8, 2) == 2.0);
+    }
+
+    #[test]
+    /// Checks if the suggestion only rates the shortlist of a long word list, and rates every word
+    /// of a short one
+    fn suggest_rates_a_shortlist() {
+        let words = test_words();
+        let suggestion = suggest(&words, &words).unwrap();
+        let best = best_guess(&words, &words).unwrap();
+
+        assert!(expected_bits(&suggestion, &words) == expected_bits(&best, &words));
+
+        // Words made of rare letters never make the shortlist of a long list
+        let mut long_list: Vec<String> = (0..GUESS_SHORTLIST)
+            .map(|i| format!("{}{}", words[i % words.len()], i))
+            .collect();
+        long_list.push(String::from("zzzzz"));
+
+        let candidates: Vec<String> = long_list[..GUESS_SHORTLIST].to_vec();
+        let guesses = shortlist(&long_list, &candidates);
+
+        assert!(guesses.len() == GUESS_SHORTLIST);
+        assert!(!guesses.contains(&String::from("zzzzz")));
+    }
+
+    #[test]
+    /// Checks if every word is given a guess count, with the solver's first guess taking one
+    fn guess_counts_cover_every_word() {
+        let words = test_words();
+        let counts = guess_counts(&words);
+
+        assert!(counts.len() == words.len());
+        assert!(counts.values().filter(|&&count| count == 1).count() == 1);
+        assert!(counts.values().all(|&count| count <= words.len()));
+    }
+}
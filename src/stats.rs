@@ -0,0 +1,251 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+use crate::timer::format_duration;
+use crate::Errors;
+
+/// The name of the file the stats are kept in, in the data directory
+pub const STATS_FILE: &str = "stats.json";
+
+/// Struct to represent the player's stats, which are kept between games
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stats {
+    /// The number of games finished
+    pub games_played: u64,
+    /// The number of games won
+    pub games_won: u64,
+    /// The fastest win, in milliseconds
+    pub best_time_ms: Option<u64>,
+    /// The fastest speedrun for each number of puzzles, in milliseconds
+    pub best_speedrun_ms: BTreeMap<usize, u64>,
+    /// The most words solved in a row in endless mode
+    pub best_endless_run: usize,
+    /// The number of hints asked for across every game
+    pub hints_used: u64,
+}
+
+impl Stats {
+    /// Reads the stats from a file. A file that doesn't exist is treated as no games played yet
+    ///
+    /// Parameters
+    /// path:   The path of the stats file
+    pub fn load(path: &Path) -> Result<Stats, Errors> {
+        match fs::read_to_string(path) {
+            Ok(contents) => {
+                serde_json::from_str(&contents).map_err(|err| Errors::InvalidStats(err.to_string()))
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Stats::default()),
+            Err(err) => Err(Errors::InvalidStats(err.to_string())),
+        }
+    }
+
+    /// Writes the stats to a file, creating the directory it is in if needed
+    ///
+    /// Parameters
+    /// path:   The path of the stats file
+    pub fn save(&self, path: &Path) -> Result<(), Errors> {
+        let to_error = |err: std::io::Error| Errors::InvalidStats(err.to_string());
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(to_error)?;
+        }
+
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|err| Errors::InvalidStats(err.to_string()))?;
+
+        fs::write(path, contents).map_err(to_error)
+    }
+
+    /// Records a finished game, returning whether it was the fastest win so far
+    ///
+    /// Parameters
+    /// won:    Whether the player guessed the word
+    /// time:   How long the game took
+    pub fn record_game(&mut self, won: bool, time: Duration) -> bool {
+        self.games_played += 1;
+
+        if !won {
+            return false;
+        }
+
+        self.games_won += 1;
+
+        is_personal_best(&mut self.best_time_ms, time)
+    }
+
+    /// Records a speedrun where every puzzle was solved, returning whether it was the fastest
+    /// speedrun of that many puzzles so far
+    ///
+    /// Parameters
+    /// puzzles:    The number of puzzles in the speedrun
+    /// time:       How long the speedrun took
+    pub fn record_speedrun(&mut self, puzzles: usize, time: Duration) -> bool {
+        let mut best = self.best_speedrun_ms.get(&puzzles).copied();
+        let personal_best = is_personal_best(&mut best, time);
+
+        if let Some(best) = best {
+            self.best_speedrun_ms.insert(puzzles, best);
+        }
+
+        personal_best
+    }
+
+    /// Records the number of words solved in an endless run, returning whether it was the longest
+    /// run so far
+    ///
+    /// Parameters
+    /// run:    The number of words solved before the run ended
+    pub fn record_endless_run(&mut self, run: usize) -> bool {
+        if run <= self.best_endless_run {
+            return false;
+        }
+
+        self.best_endless_run = run;
+
+        true
+    }
+
+    /// Returns the percentage of games that were won
+    pub fn win_percentage(&self) -> u64 {
+        match self.games_played {
+            0 => 0,
+            played => self.games_won * 100 / played,
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{:<14} {}", "Played", self.games_played)?;
+        writeln!(
+            f,
+            "{:<14} {} ({}%)",
+            "Won",
+            self.games_won,
+            self.win_percentage()
+        )?;
+
+        if let Some(best) = self.best_time_ms {
+            let best = format_duration(Duration::from_millis(best));
+            writeln!(f, "{:<14} {}", "Fastest win", best)?;
+        }
+
+        if self.hints_used > 0 {
+            writeln!(f, "{:<14} {}", "Hints used", self.hints_used)?;
+        }
+
+        if self.best_endless_run > 0 {
+            writeln!(f, "{:<14} {}", "Best run", self.best_endless_run)?;
+        }
+
+        for (puzzles, best) in &self.best_speedrun_ms {
+            let name = format!("Speedrun of {}", puzzles);
+            let best = format_duration(Duration::from_millis(*best));
+            writeln!(f, "{:<14} {}", name, best)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Replaces a best time if the new time is faster, returning whether it was
+///
+/// Parameters
+/// best:   The best time so far in milliseconds, if there is one
+/// time:   The new time
+fn is_personal_best(best: &mut Option<u64>, time: Duration) -> bool {
+    let time = time.as_millis() as u64;
+
+    match best {
+        Some(best) if *best <= time => false,
+        _ => {
+            *best = Some(time);
+            true
+        }
+    }
+}
+
+/// Loads the stats, lets them be changed, then saves them again. When there is nowhere to keep the
+/// stats they are changed without being saved
+///
+/// Parameters
+/// change:     The change to make to the stats
+pub fn update<T>(change: impl FnOnce(&mut Stats) -> T) -> Result<T, Errors> {
+    let path = match config::data_path(STATS_FILE) {
+        Some(path) => path,
+        None => return Ok(change(&mut Stats::default())),
+    };
+
+    let mut stats = Stats::load(&path)?;
+    let result = change(&mut stats);
+    stats.save(&path)?;
+
+    Ok(result)
+}
+
+/// Returns the saved stats, or empty stats if there is nowhere to keep them
+pub fn load() -> Result<Stats, Errors> {
+    match config::data_path(STATS_FILE) {
+        Some(path) => Stats::load(&path),
+        None => Ok(Stats::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Checks if only faster wins count as personal bests
+    fn record_game_tracks_fastest_win() {
+        let mut stats = Stats::default();
+
+        assert!(stats.record_game(true, Duration::from_secs(30)));
+        assert!(!stats.record_game(true, Duration::from_secs(40)));
+        assert!(!stats.record_game(false, Duration::from_secs(5)));
+        assert!(stats.record_game(true, Duration::from_secs(20)));
+
+        assert!(stats.games_played == 4);
+        assert!(stats.games_won == 3);
+        assert!(stats.best_time_ms == Some(20_000));
+        assert!(stats.win_percentage() == 75);
+    }
+
+    #[test]
+    /// Checks if speedruns of different lengths keep separate personal bests
+    fn record_speedrun_is_per_length() {
+        let mut stats = Stats::default();
+
+        assert!(stats.record_speedrun(5, Duration::from_secs(100)));
+        assert!(stats.record_speedrun(3, Duration::from_secs(200)));
+        assert!(!stats.record_speedrun(5, Duration::from_secs(150)));
+
+        assert!(stats.best_speedrun_ms[&5] == 100_000);
+        assert!(stats.best_speedrun_ms[&3] == 200_000);
+    }
+
+    #[test]
+    /// Checks if stats survive being written to a file and read back
+    fn stats_round_trip_through_file() {
+        let path =
+            std::env::temp_dir().join(format!("wordle_cli_stats_{}.json", std::process::id()));
+
+        let mut stats = Stats::default();
+        stats.record_game(true, Duration::from_secs(12));
+        stats.record_speedrun(5, Duration::from_secs(90));
+        stats.save(&path).unwrap();
+
+        let loaded = Stats::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(loaded == stats);
+    }
+}
@@ -0,0 +1,143 @@
+use clap::ValueEnum;
+use colored::{ColoredString, Colorize};
+use serde::Deserialize;
+
+use crate::letter::LetterState;
+
+/// Enum for the colour palettes the game can be drawn with
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Palette {
+    /// Green and yellow, like the original game
+    #[default]
+    Classic,
+    /// Orange and blue, which can be told apart with most forms of colourblindness
+    Colorblind,
+    /// Bold letters on solid backgrounds
+    HighContrast,
+}
+
+/// Struct to represent how the letters of a guess are styled when drawn
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Theme {
+    palette: Palette,
+    symbols: bool,
+}
+
+impl Theme {
+    /// Returns a Theme struct
+    ///
+    /// Parameters
+    /// palette:    The colour palette to draw the letters with
+    /// symbols:    Whether to also mark the state of each letter with a symbol, so that the game
+    ///             can be played without relying on colour
+    pub fn new(palette: Palette, symbols: bool) -> Theme {
+        Theme { palette, symbols }
+    }
+
+    /// Returns the palette of the theme
+    pub fn palette(&self) -> Palette {
+        self.palette
+    }
+
+    /// Returns whether the theme marks letters with symbols
+    pub fn symbols(&self) -> bool {
+        self.symbols
+    }
+
+    /// Returns the symbol used to mark a letter state
+    ///
+    /// Parameters
+    /// letter_state:   The state to get the symbol for
+    pub fn symbol(letter_state: &LetterState) -> char {
+        match letter_state {
+            LetterState::RightLetterRightPlace => '✓',
+            LetterState::RightLetterWrongPlace => '?',
+            LetterState::WrongLetterWrongPlace => '✗',
+        }
+    }
+
+    /// Colours the given text depending on the letter state and the palette of the theme
+    ///
+    /// Parameters
+    /// text:           The text to colour
+    /// letter_state:   The state of the letter the text belongs to
+    pub fn paint(&self, text: &str, letter_state: &LetterState) -> String {
+        let painted: ColoredString = match (self.palette, letter_state) {
+            (Palette::Classic, LetterState::RightLetterRightPlace) => text.bright_green(),
+            (Palette::Classic, LetterState::RightLetterWrongPlace) => text.bright_yellow(),
+            (Palette::Classic, LetterState::WrongLetterWrongPlace) => return text.to_string(),
+            (Palette::Colorblind, LetterState::RightLetterRightPlace) => {
+                text.truecolor(245, 121, 58)
+            }
+            (Palette::Colorblind, LetterState::RightLetterWrongPlace) => {
+                text.truecolor(133, 192, 249)
+            }
+            (Palette::Colorblind, LetterState::WrongLetterWrongPlace) => text.bright_black(),
+            (Palette::HighContrast, LetterState::RightLetterRightPlace) => {
+                text.black().on_bright_green().bold()
+            }
+            (Palette::HighContrast, LetterState::RightLetterWrongPlace) => {
+                text.black().on_bright_yellow().bold()
+            }
+            (Palette::HighContrast, LetterState::WrongLetterWrongPlace) => {
+                text.bright_white().on_black().bold()
+            }
+        };
+
+        painted.to_string()
+    }
+
+    /// Colours the background of the given text depending on the letter state and the palette of
+    /// the theme, used by the compact layout where letters aren't drawn in boxes
+    ///
+    /// Parameters
+    /// text:           The text to colour
+    /// letter_state:   The state of the letter the text belongs to
+    pub fn paint_background(&self, text: &str, letter_state: &LetterState) -> String {
+        let painted: ColoredString = match (self.palette, letter_state) {
+            (Palette::Classic, LetterState::RightLetterRightPlace) => {
+                text.black().on_bright_green()
+            }
+            (Palette::Classic, LetterState::RightLetterWrongPlace) => {
+                text.black().on_bright_yellow()
+            }
+            (Palette::Colorblind, LetterState::RightLetterRightPlace) => {
+                text.black().on_truecolor(245, 121, 58)
+            }
+            (Palette::Colorblind, LetterState::RightLetterWrongPlace) => {
+                text.black().on_truecolor(133, 192, 249)
+            }
+            (Palette::Classic | Palette::Colorblind, LetterState::WrongLetterWrongPlace) => {
+                text.white().on_bright_black()
+            }
+            // The high contrast palette already uses backgrounds
+            (Palette::HighContrast, _) => return self.paint(text, letter_state),
+        };
+
+        painted.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Checks that every letter state has its own symbol, so the symbols alone are enough to play
+    fn symbols_are_distinct() {
+        let right = Theme::symbol(&LetterState::RightLetterRightPlace);
+        let close = Theme::symbol(&LetterState::RightLetterWrongPlace);
+        let wrong = Theme::symbol(&LetterState::WrongLetterWrongPlace);
+
+        assert!(right != close && close != wrong && right != wrong);
+    }
+
+    #[test]
+    /// Checks that the classic palette leaves wrong letters uncoloured, like the original game
+    fn classic_leaves_wrong_letters_plain() {
+        let theme = Theme::default();
+
+        assert!(theme.paint("│ W │", &LetterState::WrongLetterWrongPlace) == "│ W │");
+    }
+}
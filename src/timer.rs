@@ -0,0 +1,155 @@
+use std::time::{Duration, Instant};
+
+/// Struct to represent the clock a game is played against. The stopwatch counts up from the start
+/// of the game, and the optional countdowns limit how long the whole game and each guess can take
+#[derive(Clone, Copy, Debug)]
+pub struct Timer {
+    started: Instant,
+    guess_started: Instant,
+    stopped: Option<Duration>,
+    carried: Duration,
+    time_limit: Option<Duration>,
+    guess_time_limit: Option<Duration>,
+}
+
+impl Timer {
+    /// Returns a Timer struct, which starts counting straight away
+    ///
+    /// Parameters
+    /// time_limit:         How long the whole game can take, if it is limited
+    /// guess_time_limit:   How long each guess can take, if it is limited
+    pub fn new(time_limit: Option<Duration>, guess_time_limit: Option<Duration>) -> Timer {
+        let now = Instant::now();
+
+        Timer {
+            started: now,
+            guess_started: now,
+            stopped: None,
+            carried: Duration::ZERO,
+            time_limit,
+            guess_time_limit,
+        }
+    }
+
+    /// Returns the timer with time carried over from earlier games added to its total, for games
+    /// played one after another such as in a speedrun
+    ///
+    /// Parameters
+    /// carried:    The time taken by the earlier games
+    pub fn with_carried(mut self, carried: Duration) -> Timer {
+        self.carried = carried;
+        self
+    }
+
+    /// Returns how long the game has taken so far, or how long it took once the timer is stopped
+    pub fn elapsed(&self) -> Duration {
+        self.stopped.unwrap_or_else(|| self.started.elapsed())
+    }
+
+    /// Returns the time taken by this game and any earlier games carried over into it
+    pub fn total(&self) -> Duration {
+        self.carried + self.elapsed()
+    }
+
+    /// Starts the countdown for the next guess again
+    pub fn next_guess(&mut self) {
+        self.guess_started = Instant::now();
+    }
+
+    /// Stops the timer, so the time the game took stays the same from then on
+    pub fn stop(&mut self) {
+        if self.stopped.is_none() {
+            self.stopped = Some(self.started.elapsed());
+        }
+    }
+
+    /// Returns whether the timer is limited by either countdown
+    pub fn has_limit(&self) -> bool {
+        self.time_limit.is_some() || self.guess_time_limit.is_some()
+    }
+
+    /// Returns how long is left before a countdown runs out, taking whichever countdown runs out
+    /// first. Returns None if there is no countdown
+    pub fn remaining(&self) -> Option<Duration> {
+        let game_left = self
+            .time_limit
+            .map(|limit| limit.saturating_sub(self.elapsed()));
+        let guess_left = self
+            .guess_time_limit
+            .map(|limit| limit.saturating_sub(self.guess_started.elapsed()));
+
+        match (game_left, guess_left) {
+            (Some(game_left), Some(guess_left)) => Some(game_left.min(guess_left)),
+            (left, None) | (None, left) => left,
+        }
+    }
+
+    /// Returns whether a countdown has run out
+    pub fn is_expired(&self) -> bool {
+        self.stopped.is_none() && self.remaining() == Some(Duration::ZERO)
+    }
+
+    /// Returns the line showing the clock, with the time left when there is a countdown
+    pub fn clock_line(&self) -> String {
+        match self.remaining() {
+            Some(left) => format!(
+                "⏱ {}    {} left",
+                format_duration(self.total()),
+                format_duration(left)
+            ),
+            None => format!("⏱ {}", format_duration(self.total())),
+        }
+    }
+}
+
+/// Returns a duration as seconds to one decimal place, with minutes once it is a minute or longer
+///
+/// Parameters
+/// duration:   The duration to format
+pub fn format_duration(duration: Duration) -> String {
+    let tenths = duration.as_millis() / 100;
+    let minutes = tenths / 600;
+    let seconds = (tenths % 600) / 10;
+
+    match minutes {
+        0 => format!("{}.{}s", seconds, tenths % 10),
+        _ => format!("{}:{:02}.{}", minutes, seconds, tenths % 10),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Checks if the countdown that runs out first is the one that is shown
+    fn remaining_uses_shortest_countdown() {
+        let timer = Timer::new(Some(Duration::from_secs(60)), Some(Duration::from_secs(10)));
+        assert!(timer.remaining().unwrap() <= Duration::from_secs(10));
+        assert!(!timer.is_expired());
+
+        let timer = Timer::new(None, Some(Duration::ZERO));
+        assert!(timer.is_expired());
+
+        assert!(Timer::new(None, None).remaining().is_none());
+    }
+
+    #[test]
+    /// Checks if a stopped timer keeps the time it was stopped at, and never expires
+    fn stopped_timer_keeps_its_time() {
+        let mut timer = Timer::new(Some(Duration::ZERO), None).with_carried(Duration::from_secs(5));
+        timer.stop();
+
+        let elapsed = timer.elapsed();
+        assert!(timer.elapsed() == elapsed);
+        assert!(timer.total() == elapsed + Duration::from_secs(5));
+        assert!(!timer.is_expired());
+    }
+
+    #[test]
+    /// Checks if durations are shown with minutes only when needed
+    fn durations_are_formatted() {
+        assert!(format_duration(Duration::from_millis(4_250)) == "4.2s");
+        assert!(format_duration(Duration::from_millis(83_400)) == "1:23.4");
+    }
+}
@@ -0,0 +1,657 @@
+use std::io::{self, stdout, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, execute, queue, style::Print, terminal};
+
+use colored::Colorize;
+
+use crate::animation::{self, Effect};
+use crate::duel::Duel;
+use crate::game::Game;
+use crate::hint::{self, HintKind};
+use crate::render::{grid_renderer, side_by_side, visible_width, Renderer};
+use crate::solver;
+use crate::timer::{format_duration, Timer};
+use crate::{
+    build_error_message, candidate_rows, compact_layout, words_left_line, GameOptions, LIST_KEY,
+};
+
+/// How often the frame is redrawn while a clock is shown, so the clock keeps moving between key
+/// presses
+const CLOCK_TICK: Duration = Duration::from_millis(100);
+
+/// Struct to represent the terminal while the game is drawn full screen. The terminal is put back
+/// the way it was when the struct is dropped, including when the game panics
+pub struct Tui {
+    _private: (),
+}
+
+impl Tui {
+    /// Switches the terminal to the alternate screen and raw mode, returning a Tui struct
+    pub fn enter() -> io::Result<Tui> {
+        terminal::enable_raw_mode()?;
+        execute!(stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+
+        Ok(Tui { _private: () })
+    }
+
+    /// Clears the screen and draws a frame in the middle of it. Lines that don't fit on the screen
+    /// are left off the bottom
+    ///
+    /// Parameters
+    /// lines:  The lines of the frame, which can contain colour codes
+    pub fn draw(&mut self, lines: &[String]) -> io::Result<()> {
+        let (columns, rows) = terminal::size()?;
+
+        let frame_width = lines
+            .iter()
+            .map(|line| visible_width(line))
+            .max()
+            .unwrap_or(0);
+        let left = (columns as usize).saturating_sub(frame_width) / 2;
+        let top = (rows as usize).saturating_sub(lines.len()) / 2;
+
+        let mut stdout = stdout();
+
+        queue!(stdout, terminal::Clear(terminal::ClearType::All))?;
+
+        for (i, line) in lines.iter().take(rows as usize).enumerate() {
+            queue!(
+                stdout,
+                cursor::MoveTo(left as u16, (top + i) as u16),
+                Print(line)
+            )?;
+        }
+
+        stdout.flush()
+    }
+}
+
+impl Drop for Tui {
+    fn drop(&mut self) {
+        // Errors are ignored here as there is nothing left to do about them
+        let _ = execute!(stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Enum for what a key press means to the game
+enum Input {
+    Letter(char),
+    Hint(HintKind),
+    List,
+    Backspace,
+    Submit,
+    Quit,
+    Other,
+}
+
+impl From<KeyEvent> for Input {
+    fn from(key: KeyEvent) -> Input {
+        match key.code {
+            // Raw mode stops ctrl-c from interrupting the program, so it has to be handled here
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Input::Quit,
+            KeyCode::Char(LIST_KEY) => Input::List,
+            KeyCode::Char(key) => match HintKind::from_key(key) {
+                Some(kind) => Input::Hint(kind),
+                None if key.is_alphabetic() => Input::Letter(key),
+                None => Input::Other,
+            },
+            KeyCode::Backspace => Input::Backspace,
+            KeyCode::Enter => Input::Submit,
+            KeyCode::Esc => Input::Quit,
+            _ => Input::Other,
+        }
+    }
+}
+
+/// Plays the game full screen until the game is over or the player quits, redrawing the whole
+/// frame whenever a key is pressed or the terminal is resized. While a clock is shown the frame is
+/// also redrawn as the clock ticks, and running out of time ends the game
+///
+/// Parameters
+/// game:       The game to play
+/// options:    The options the game is played with
+/// timer:      The clock the game is played against
+/// title:      A line to show above the game, such as which round of a run it is
+pub fn run(
+    game: &mut Game,
+    options: &GameOptions,
+    timer: &mut Timer,
+    title: Option<&str>,
+) -> io::Result<()> {
+    let mut tui = Tui::enter()?;
+
+    let mut input = String::new();
+    let mut message: Option<String> = None;
+    let mut listed = false;
+    let show_clock = options.stopwatch || timer.has_limit();
+
+    loop {
+        if timer.is_expired() {
+            game.time_out();
+        }
+
+        if game.is_over() {
+            timer.stop();
+        }
+
+        let clock = timer.clock_line();
+        let clock = show_clock.then_some(clock.as_str());
+
+        // The layout is decided on every frame, as the terminal can be resized at any time
+        let height =
+            full_height(game, options) + usize::from(show_clock) + usize::from(title.is_some());
+        let compact = compact_layout(options.compact, height);
+
+        let lines = frame(
+            game,
+            options,
+            compact,
+            &input,
+            message.as_deref(),
+            clock,
+            None,
+        );
+
+        let lines = with_title(lines, title);
+        tui.draw(&with_candidates(lines, game, options, listed))?;
+
+        // Without a clock there is nothing to redraw until a key is pressed
+        if show_clock && !event::poll(CLOCK_TICK)? {
+            continue;
+        }
+
+        let key = match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            // Resizing only needs the frame to be redrawn for the new size
+            Event::Resize(_, _) => continue,
+            _ => continue,
+        };
+
+        // Once the game is over any key exits, leaving the final grid on screen until then
+        if game.is_over() {
+            return Ok(());
+        }
+
+        // Any message is cleared on the next key press, after the player has had time to read it
+        message = None;
+
+        match Input::from(key) {
+            Input::Letter(letter) => {
+                if input.chars().count() < game.word_length() {
+                    input.extend(letter.to_lowercase());
+                }
+            }
+            Input::Hint(kind) => {
+                let hints_left = game.hints_left();
+
+                message = Some(match game.hint(kind) {
+                    Some(hint) => hint.to_string(),
+                    None => hint::no_hint_message(hints_left).to_string(),
+                });
+            }
+            Input::List => listed = options.practice && !listed,
+            Input::Backspace => {
+                input.pop();
+            }
+            Input::Submit => {
+                // Only whether the guess was valid matters here, as the frame is drawn from the game
+                let result = game.guess(&input).map(|_| ());
+                let valid = result.is_ok();
+
+                let effects = match result {
+                    Ok(_) if game.is_won() => {
+                        let mut effects = animation::reveal_frames(game.word_length());
+                        effects.extend(animation::bounce_frames(game.word_length()));
+                        effects
+                    }
+                    Ok(_) => animation::reveal_frames(game.word_length()),
+                    Err(err) => {
+                        message = Some(build_error_message(&err, game.word_length()));
+                        animation::shake_frames()
+                    }
+                };
+
+                if options.animations {
+                    for effect in effects {
+                        let clock_line = timer.clock_line();
+                        let lines = frame(
+                            game,
+                            options,
+                            compact,
+                            &input,
+                            message.as_deref(),
+                            show_clock.then_some(clock_line.as_str()),
+                            Some(&effect),
+                        );
+
+                        let lines = with_title(lines, title);
+                        tui.draw(&with_candidates(lines, game, options, listed))?;
+                        thread::sleep(animation::FRAME_DELAY);
+                    }
+                }
+
+                // The countdown for the next guess starts once the animation has finished
+                if valid {
+                    timer.next_guess();
+                }
+
+                input.clear();
+            }
+            Input::Quit => return Ok(()),
+            Input::Other => (),
+        }
+    }
+}
+
+/// Plays a duel full screen until both games are over or the players quit. Each player types
+/// their guess on their own turn, and only the time spent on their own turns counts against them
+///
+/// Parameters
+/// duel:       The duel to play
+/// options:    The options the duel is played with
+pub fn run_duel(duel: &mut Duel, options: &GameOptions) -> io::Result<()> {
+    let mut tui = Tui::enter()?;
+
+    let mut input = String::new();
+    let mut message: Option<String> = None;
+    let mut turn_started = Instant::now();
+
+    loop {
+        let height = full_height(duel.current().game(), options) + 1;
+        let compact = compact_layout(options.compact, height);
+
+        tui.draw(&duel_frame(
+            duel,
+            options,
+            compact,
+            &input,
+            message.as_deref(),
+            None,
+        ))?;
+
+        let key = match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            Event::Resize(_, _) => continue,
+            _ => continue,
+        };
+
+        if duel.is_over() {
+            return Ok(());
+        }
+
+        message = None;
+        let word_length = duel.current().game().word_length();
+
+        match Input::from(key) {
+            Input::Letter(letter) => {
+                if input.chars().count() < word_length {
+                    input.extend(letter.to_lowercase());
+                }
+            }
+            Input::Backspace => {
+                input.pop();
+            }
+            Input::Submit => {
+                // The turn passes on a valid guess, so the animation is played on the board of the
+                // player who guessed rather than whoever's turn it is next
+                let guesser = duel.turn();
+                let result = duel.guess(&input, turn_started.elapsed()).map(|_| ());
+                let valid = result.is_ok();
+
+                let effects = match result {
+                    Ok(_) if duel.players()[guesser].game().is_won() => {
+                        let mut effects = animation::reveal_frames(word_length);
+                        effects.extend(animation::bounce_frames(word_length));
+                        effects
+                    }
+                    Ok(_) => animation::reveal_frames(word_length),
+                    Err(err) => {
+                        message = Some(build_error_message(&err, word_length));
+                        animation::shake_frames()
+                    }
+                };
+
+                if options.animations {
+                    for effect in effects {
+                        let lines = duel_frame(
+                            duel,
+                            options,
+                            compact,
+                            &input,
+                            message.as_deref(),
+                            Some((guesser, &effect)),
+                        );
+
+                        tui.draw(&lines)?;
+                        thread::sleep(animation::FRAME_DELAY);
+                    }
+                }
+
+                // Animations aren't counted towards the next player's time
+                if valid {
+                    turn_started = Instant::now();
+                }
+
+                input.clear();
+            }
+            Input::Hint(kind) => {
+                let hints_left = duel.current().game().hints_left();
+
+                message = Some(match duel.hint(kind) {
+                    Some(hint) => hint.to_string(),
+                    None => hint::no_hint_message(hints_left).to_string(),
+                });
+            }
+            Input::Quit => return Ok(()),
+            // Duels are a race, so neither player gets a list of the words left
+            Input::List | Input::Other => (),
+        }
+    }
+}
+
+/// Returns the number of lines the frame needs when the guesses are drawn in boxes
+///
+/// Parameters
+/// game:       The game being drawn
+/// options:    The options the game is played with
+fn full_height(game: &Game, options: &GameOptions) -> usize {
+    let word_line = usize::from(options.show_word);
+
+    let hint_line = usize::from(game.max_hints() > 0);
+    let practice_line = usize::from(options.practice);
+
+    // Three lines for each guess, then a gap, the prompt and a line for messages
+    word_line + game.max_guesses() * 3 + 3 + hint_line + practice_line
+}
+
+/// Returns the lines of the frame for the current state of the game
+///
+/// Parameters
+/// game:       The game to draw
+/// options:    The options the game is played with
+/// compact:    Whether to draw each guess on one line instead of in boxes
+/// input:      What the player has typed so far
+/// message:    A message for the player, such as why their guess wasn't valid
+/// clock:      The line showing the clock, if it is shown
+/// effect:     The frame of an animation to draw, if one is playing
+fn frame(
+    game: &Game,
+    options: &GameOptions,
+    compact: bool,
+    input: &str,
+    message: Option<&str>,
+    clock: Option<&str>,
+    effect: Option<&Effect>,
+) -> Vec<String> {
+    let theme = &options.theme;
+    let mut lines: Vec<String> = Vec::new();
+
+    if options.show_word {
+        lines.push(game.word_to_guess().bold().to_string());
+    }
+
+    if let Some(clock) = clock {
+        lines.push(clock.to_string());
+    }
+
+    let grid = grid_renderer(*theme, compact).render(game, effect);
+
+    lines.extend(grid);
+    lines.push(String::new());
+
+    // Once a hint is used what it showed stays on screen, until then the line says how to ask
+    if !game.hints().is_empty() {
+        lines.push(hint::hint_line(game.hints(), game.word_length()));
+    } else if game.max_hints() > 0 {
+        let help = match game.is_over() {
+            true => String::new(),
+            false => hint::help_line(game.hints_left()),
+        };
+
+        lines.push(help);
+    }
+
+    // The prompt is left showing the invalid guess while it shakes
+    if let Some(Effect::Shake { offset }) = effect {
+        let typed = input.to_uppercase();
+
+        lines.push(format!("{}> {}", " ".repeat(*offset), typed));
+        lines.push(message.unwrap_or("").to_string());
+    } else if game.is_over() {
+        let time_up = match game.is_timed_out() {
+            true => "Time's up! ",
+            false => "",
+        };
+
+        lines.push(format!(
+            "{}The word was {}",
+            time_up,
+            game.word_to_guess().bold()
+        ));
+        lines.push(String::from("Press any key to continue"));
+    } else {
+        // Underscores show how many letters are left to type
+        let typed = input.to_uppercase();
+        let blanks = "_".repeat(game.word_length().saturating_sub(input.chars().count()));
+
+        lines.push(format!("> {}{}", typed, blanks));
+        lines.push(message.unwrap_or("").to_string());
+    }
+
+    lines
+}
+
+/// Returns the frame with a title above it, or the frame as it is when there is no title
+///
+/// Parameters
+/// lines:      The lines of the frame
+/// title:      The line to show above the frame, if there is one
+fn with_title(mut lines: Vec<String>, title: Option<&str>) -> Vec<String> {
+    if let Some(title) = title {
+        lines.insert(0, title.bold().to_string());
+    }
+
+    lines
+}
+
+/// Returns the frame with the number of words that are still possible under it in practice, and
+/// the words themselves next to it when they are listed. Outside of practice the frame is left as
+/// it is
+///
+/// Parameters
+/// lines:      The lines of the frame
+/// game:       The game being drawn
+/// options:    The options the game is played with
+/// listed:     Whether the player asked for the words to be listed
+fn with_candidates(
+    mut lines: Vec<String>,
+    game: &Game,
+    options: &GameOptions,
+    listed: bool,
+) -> Vec<String> {
+    if !options.practice {
+        return lines;
+    }
+
+    let candidates = solver::remaining(game.words(), game.guesses());
+    let action = match listed {
+        true => "hide",
+        false => "list",
+    };
+
+    lines.push(format!(
+        "{}, press {} to {} them",
+        words_left_line(candidates.len()),
+        LIST_KEY,
+        action
+    ));
+
+    if !listed {
+        return lines;
+    }
+
+    // The list is only as tall as the frame, with the words that don't fit counted at the bottom
+    let height = lines.len().max(2);
+    let mut rows = candidate_rows(&candidates, 4);
+
+    if rows.len() > height {
+        rows.truncate(height - 1);
+        rows.push(format!("and {} more", candidates.len() - (height - 1) * 4));
+    }
+
+    side_by_side(&[lines, rows], 4)
+}
+
+/// Returns the lines of the frame for the current state of a duel, with the boards of both
+/// players next to each other. Both players share the screen, so until the duel is over only the
+/// board of the player at the keyboard shows its letters, and the other board only shows colours
+///
+/// Parameters
+/// duel:       The duel to draw
+/// options:    The options the duel is played with
+/// compact:    Whether to draw each guess on one line instead of in boxes
+/// input:      What the current player has typed so far
+/// message:    A message for the player, such as why their guess wasn't valid
+/// effect:     The player an animation is playing for and the frame to draw, if one is playing
+fn duel_frame(
+    duel: &Duel,
+    options: &GameOptions,
+    compact: bool,
+    input: &str,
+    message: Option<&str>,
+    effect: Option<(usize, &Effect)>,
+) -> Vec<String> {
+    let renderer = grid_renderer(options.theme, compact);
+    let mut lines: Vec<String> = Vec::new();
+
+    if options.show_word {
+        lines.push(duel.word_to_guess().bold().to_string());
+    }
+
+    let boards: Vec<Vec<String>> = duel
+        .players()
+        .iter()
+        .enumerate()
+        .map(|(i, player)| {
+            // An arrow points at the player whose turn it is
+            let marker = match i == duel.turn() && !duel.is_over() {
+                true => "▶",
+                false => " ",
+            };
+            let mut board = vec![format!(
+                "{} {} {}",
+                marker,
+                player.name().bold(),
+                format_duration(player.time())
+            )];
+
+            // The player who just guessed sees their guess revealed before the turn passes on
+            let player_effect = effect.filter(|(player, _)| *player == i).map(|(_, e)| e);
+            let at_keyboard = match effect {
+                Some((player, _)) => player == i,
+                None => i == duel.turn(),
+            };
+
+            match at_keyboard || duel.is_over() {
+                true => board.extend(renderer.render(player.game(), player_effect)),
+                false => board.extend(hidden_board(renderer.as_ref(), player.game())),
+            }
+
+            board
+        })
+        .collect();
+
+    lines.extend(side_by_side(&boards, 4));
+    lines.push(String::new());
+
+    // Only the hints of the player at the keyboard are shown, the same as their letters
+    let game = duel.current().game();
+
+    if !duel.is_over() && !game.hints().is_empty() {
+        lines.push(hint::hint_line(game.hints(), game.word_length()));
+    } else if !duel.is_over() && game.max_hints() > 0 {
+        lines.push(hint::help_line(game.hints_left()));
+    }
+
+    if let Some((_, Effect::Shake { offset })) = effect {
+        lines.push(format!(
+            "{}{} > {}",
+            " ".repeat(*offset),
+            duel.current().name(),
+            input.to_uppercase()
+        ));
+        lines.push(message.unwrap_or("").to_string());
+    } else if duel.is_over() {
+        lines.push(duel.summary());
+        lines.push(String::from("Press any key to exit"));
+    } else {
+        let typed = input.to_uppercase();
+        let word_length = duel.current().game().word_length();
+        let blanks = "_".repeat(word_length.saturating_sub(input.chars().count()));
+
+        lines.push(format!("{} > {}{}", duel.current().name(), typed, blanks));
+        lines.push(message.unwrap_or("").to_string());
+    }
+
+    lines
+}
+
+/// Returns the lines that draw a game with the letters of every guess blanked out, leaving only
+/// the colours they were marked with
+///
+/// Parameters
+/// renderer:   The renderer to draw the guesses with
+/// game:       The game to draw
+fn hidden_board(renderer: &dyn Renderer, game: &Game) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+
+    for word in game.guesses() {
+        lines.extend(renderer.render_guess(&word.hidden()));
+    }
+
+    for _i in 0..game.remaining_guesses() {
+        lines.extend(renderer.render_blank(game.word_length()));
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{PartialSettings, Settings};
+
+    #[test]
+    /// Checks if the board of the player waiting for their turn doesn't show their guesses
+    fn duel_hides_waiting_players_letters() {
+        let words = vec![
+            String::from("cigar"),
+            String::from("crane"),
+            String::from("moist"),
+        ];
+        let options = Settings::resolve(PartialSettings::default(), PartialSettings::default())
+            .game_options();
+        let mut duel = Duel::new(
+            [String::from("alice"), String::from("bob")],
+            String::from("cigar"),
+            words,
+            &options,
+        );
+
+        duel.guess("crane", Duration::ZERO).unwrap();
+        let frame = duel_frame(&duel, &options, false, "", None, None).join("\n");
+
+        // Alice guessed crane, and it's bob's turn
+        assert!(!frame.contains('C') && !frame.contains('R') && !frame.contains('N'));
+
+        duel.guess("moist", Duration::ZERO).unwrap();
+        let frame = duel_frame(&duel, &options, false, "", None, None).join("\n");
+
+        assert!(frame.contains("│ C │"));
+        assert!(!frame.contains('M') && !frame.contains('O'));
+    }
+}
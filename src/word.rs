@@ -1,10 +1,14 @@
 use std::cmp::Ordering;
-use std::io::stdout;
+use std::fmt;
 
-use crossterm::{execute, style::Print};
+use rand::seq::SliceRandom;
+use rand::Rng;
 
 use crate::letter::Letter;
 use crate::letter::LetterState;
+use crate::scoring::{Pegs, Scoring};
+use crate::solver;
+use crate::theme::Theme;
 
 /// Enum for possible errors
 #[derive(Debug)]
@@ -13,11 +17,16 @@ pub enum BuildErrors {
     TooShortOfWord,
     TooLongOfWord,
     NonValidWord,
+    IgnoredHint,
 }
 
 /// Struct to represent a wordle guess as a word
 pub struct Word {
     letters: Vec<Letter>,
+    scoring: Scoring,
+    // The position of the letter that was marked wrongly and the state it really has, if the
+    // scoring lies
+    lie: Option<(usize, LetterState)>,
 }
 
 impl Word {
@@ -26,11 +35,20 @@ impl Word {
     /// Parameters
     /// word:         The word the struct represents
     /// right_word:   The word being guessed
-    /// words:        The wordlist the player is guessing from. Userd to check if the guess is a
-    ///               valid word
+    /// words:        The wordlist the player is guessing from, already case folded. Userd to check
+    ///               if the guess is a valid word
+    ///
+    /// The guess and the right word are compared case-insensitively
     pub fn new(word: String, right_word: &str, words: &[String]) -> Result<Word, BuildErrors> {
+        // Case folding the guess and right word so that "CRANE" and "crane" are scored the same
+        let word = word.to_lowercase();
+        let right_word = right_word.to_lowercase();
+
+        // The length of the right word decides how long guesses have to be
+        let word_length = right_word.chars().count();
+
         // Checks if the word is too short or too long, returning the appropriate error if it is
-        match word.len().cmp(&5) {
+        match word.chars().count().cmp(&word_length) {
             Ordering::Greater => return Err(BuildErrors::TooLongOfWord),
             Ordering::Less => return Err(BuildErrors::TooShortOfWord),
             _ => (),
@@ -44,20 +62,13 @@ impl Word {
 
         let mut letters: Vec<Letter> = Vec::new();
 
-        let mut word_array: [Option<char>; 5] = [None; 5];
-        let mut right_word_array: [Option<char>; 5] = [None; 5];
-        let mut letter_array: [Option<Letter>; 5] = [None, None, None, None, None];
-
-        // Collecting the submitted word into an array
-        for i in 0..5 {
-            word_array[i] = word.chars().nth(i);
-        }
-        for i in 0..5 {
-            right_word_array[i] = right_word.chars().nth(i);
-        }
+        // Collecting the submitted word and the right word into vecs
+        let mut word_array: Vec<Option<char>> = word.chars().map(Some).collect();
+        let mut right_word_array: Vec<Option<char>> = right_word.chars().map(Some).collect();
+        let mut letter_array: Vec<Option<Letter>> = (0..word_length).map(|_| None).collect();
 
-        // Adding the right word wrong place letters 
-        for i in 0..5 {
+        // Adding the right word right place letters
+        for i in 0..word_length {
             if word_array[i] == Some(right_word_letters[i]) {
                 letter_array[i] = Some(Letter::new(
                     right_word_letters[i],
@@ -72,7 +83,7 @@ impl Word {
         }
 
         // Adding the right word wrong place letters
-        for i in 0..5 {
+        for i in 0..word_length {
             match word_array[i] {
                 Some(letter) => {
                     if right_word_array.contains(&Some(letter)) {
@@ -97,7 +108,7 @@ impl Word {
         }
 
         // Adding the rest of the letters
-        for i in 0..5 {
+        for i in 0..word_length {
             match word_array[i] {
                 Some(letter) => {
                     letter_array[i] = Some(Letter::new(letter, LetterState::WrongLetterWrongPlace));
@@ -110,26 +121,301 @@ impl Word {
             letters.push(letter.unwrap());
         }
 
-        Ok(Word { letters })
+        Ok(Word {
+            letters,
+            scoring: Scoring::default(),
+            lie: None,
+        })
+    }
+
+    /// Returns the word scored by a different set of rules. The letters are still scored one by
+    /// one, but only the feedback the rules allow is shown. Rules that lie mark one letter, chosen
+    /// at random, with one of the states it doesn't have, unless the word is the answer
+    ///
+    /// Parameters
+    /// scoring:    The rules the word is scored by
+    pub fn with_scoring(mut self, scoring: Scoring) -> Word {
+        self.scoring = scoring;
+
+        // A guess that found the answer isn't lied about, as it ends the game
+        let solved = self
+            .letters
+            .iter()
+            .all(|letter| letter.state() == LetterState::RightLetterRightPlace);
+
+        if scoring == Scoring::Fibble && self.lie.is_none() && !solved {
+            let mut rng = rand::thread_rng();
+
+            let index = rng.gen_range(0..self.letters.len());
+            let letter = *self.letters[index].letter();
+            let truth = self.letters[index].state();
+
+            let wrong_states: Vec<LetterState> = [
+                LetterState::WrongLetterWrongPlace,
+                LetterState::RightLetterWrongPlace,
+                LetterState::RightLetterRightPlace,
+            ]
+            .into_iter()
+            .filter(|state| *state != truth)
+            .collect();
+
+            let shown = *wrong_states.choose(&mut rng).unwrap();
+
+            self.letters[index] = Letter::new(letter, shown);
+            self.lie = Some((index, truth));
+        }
+
+        self
+    }
+
+    /// Returns the word with one letter marked wrongly on purpose, to show a lie the scoring told
+    /// when the word was first played again. Letters past the end of the word can't be lied about
+    ///
+    /// Parameters
+    /// index:  The position of the letter that was lied about
+    /// shown:  The state the letter was shown with
+    pub fn with_lie(mut self, index: usize, shown: LetterState) -> Word {
+        if let Some(letter) = self.letters.get(index) {
+            let truth = letter.state();
+
+            self.letters[index] = Letter::new(*letter.letter(), shown);
+            self.lie = Some((index, truth));
+        }
+
+        self
+    }
+
+    /// Returns a Word struct from letters that have already been scored, such as a guess scored
+    /// by a server
+    ///
+    /// Parameters
+    /// letters:    The scored letters of the word
+    pub fn from_letters(letters: Vec<Letter>) -> Word {
+        Word {
+            letters,
+            scoring: Scoring::default(),
+            lie: None,
+        }
+    }
+
+    /// Returns a guess written in plain text notation as a Word struct, the reverse of plain(). A
+    /// letter in square brackets is in the right place, a letter in parentheses is in the wrong
+    /// place and any other letter isn't in the word. Returns None if the notation isn't valid
+    ///
+    /// Parameters
+    /// row:    The guess in plain text notation, such as [C](R)ane
+    pub fn from_plain(row: &str) -> Option<Word> {
+        let mut letters: Vec<Letter> = Vec::new();
+        let mut chars = row.trim().chars();
+
+        while let Some(c) = chars.next() {
+            let (letter, state) = match c {
+                '[' | '(' => {
+                    let letter = chars.next()?;
+                    let (close, state) = match c {
+                        '[' => (']', LetterState::RightLetterRightPlace),
+                        _ => (')', LetterState::RightLetterWrongPlace),
+                    };
+
+                    if chars.next()? != close {
+                        return None;
+                    }
+
+                    (letter, state)
+                }
+                letter => (letter, LetterState::WrongLetterWrongPlace),
+            };
+
+            if !letter.is_alphabetic() {
+                return None;
+            }
+
+            letters.push(Letter::new(letter.to_lowercase().next()?, state));
+        }
+
+        match letters.is_empty() {
+            true => None,
+            false => Some(Word::from_letters(letters)),
+        }
+    }
+
+    /// Returns whether a guess makes use of every hint this word revealed, which is what hard mode
+    /// requires. Letters in the right place have to stay in that place, and letters in the wrong
+    /// place have to be used somewhere in the guess. With other scoring rules, the guess has to be
+    /// a word that could be the answer given the feedback this word got
+    ///
+    /// Parameters
+    /// guess:    The guess to check, already case folded
+    pub fn hints_used_by(&self, guess: &str) -> bool {
+        if self.scoring != Scoring::Letters {
+            return solver::is_consistent(guess, self);
+        }
+
+        let mut guess_letters: Vec<Option<char>> = guess.chars().map(Some).collect();
+
+        // Checking the right place letters first, so they can't be counted for a wrong place letter
+        for (i, letter) in self.letters.iter().enumerate() {
+            if letter.state() == LetterState::RightLetterRightPlace {
+                if guess_letters.get(i) != Some(&Some(*letter.letter())) {
+                    return false;
+                }
+
+                guess_letters[i] = None;
+            }
+        }
+
+        for letter in &self.letters {
+            if letter.state() == LetterState::RightLetterWrongPlace {
+                match guess_letters
+                    .iter()
+                    .position(|&guess_letter| guess_letter == Some(*letter.letter()))
+                {
+                    Some(index) => guess_letters[index] = None,
+                    None => return false,
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Returns the letters of the word, marked with the feedback the player was shown
+    pub fn letters(&self) -> &[Letter] {
+        &self.letters
+    }
+
+    /// Returns the position of the letter that was marked wrongly, if the scoring lied
+    pub fn lie(&self) -> Option<usize> {
+        self.lie.map(|(index, _)| index)
+    }
+
+    /// Returns the word with every letter marked with its true state, to show the player once the
+    /// game is over
+    pub fn revealed(&self) -> Word {
+        let letters: Vec<Letter> = self
+            .letters
+            .iter()
+            .enumerate()
+            .map(|(i, letter)| match self.lie {
+                Some((index, truth)) if index == i => Letter::new(*letter.letter(), truth),
+                _ => Letter::new(*letter.letter(), letter.state()),
+            })
+            .collect();
+
+        Word::from_letters(letters)
+    }
+
+    /// Returns the word with its letters blanked out, keeping the feedback each letter was shown,
+    /// so a board can be drawn without giving away what was guessed
+    pub fn hidden(&self) -> Word {
+        let letters: Vec<Letter> = self
+            .letters
+            .iter()
+            .map(|letter| Letter::new(' ', letter.state()))
+            .collect();
+
+        Word {
+            letters,
+            scoring: self.scoring,
+            lie: None,
+        }
+    }
+
+    /// Returns the rules the word is scored by
+    pub fn scoring(&self) -> Scoring {
+        self.scoring
+    }
+
+    /// Returns the number of letters in the right place and in the wrong place
+    pub fn pegs(&self) -> Pegs {
+        Pegs::from_letters(&self.letters)
+    }
+
+    /// Returns the word in plain text notation, for output that can't show colours
+    pub fn plain(&self) -> String {
+        match self.scoring {
+            Scoring::Letters | Scoring::Fibble => {
+                self.letters.iter().map(|letter| letter.plain()).collect()
+            }
+            Scoring::Mastermind => format!("{}  {}", self.to_string().to_uppercase(), self.pegs()),
+        }
     }
 
-    /// Prints the word to the console
-    pub fn print(&self) {
-        let mut display = String::new();
+    /// Returns the rows of boxes the word is drawn with
+    ///
+    /// Parameters
+    /// theme:    The theme to style the letters with
+    pub fn rows(&self, theme: &Theme) -> Vec<String> {
+        if self.scoring == Scoring::Mastermind {
+            return self.unscored_rows();
+        }
+
+        let mut rows: Vec<String> = Vec::new();
 
         for i in 0..3 {
+            let mut row = String::new();
+
             for letter in &self.letters {
-                display.push_str(&letter.get_row(i).unwrap());
+                row.push_str(&letter.get_row(i, theme).unwrap());
             }
 
-            display.push('\n');
+            rows.push(row);
         }
 
-        execute! {
-            stdout(),
-            Print(display)
+        rows
+    }
+
+    /// Returns the word as a single row of coloured cells, used by the compact layout
+    ///
+    /// Parameters
+    /// theme:    The theme to style the letters with
+    pub fn compact_row(&self, theme: &Theme) -> String {
+        if self.scoring == Scoring::Mastermind {
+            let letters: String = self
+                .letters
+                .iter()
+                .map(|letter| format!(" {} ", letter.letter().to_uppercase()))
+                .collect();
+
+            return format!("{}  {}", letters, self.pegs().symbols(self.letters.len()));
         }
-        .unwrap();
+
+        self.letters
+            .iter()
+            .map(|letter| letter.get_compact(theme))
+            .collect()
+    }
+
+    /// Returns the rows of boxes for a word scored by its pegs, with the letters left uncoloured
+    /// and the pegs beside the middle row
+    fn unscored_rows(&self) -> Vec<String> {
+        let length = self.letters.len();
+        let pegs = self.pegs().symbols(length);
+
+        // The top and bottom rows are padded so every row is as wide as the middle one
+        let padding = " ".repeat(pegs.chars().count() + 2);
+
+        let letters: String = self
+            .letters
+            .iter()
+            .map(|letter| format!("│ {} │", letter.letter().to_uppercase()))
+            .collect();
+
+        vec![
+            format!("{}{}", "╭───╮".repeat(length), padding),
+            format!("{}  {}", letters, pegs),
+            format!("{}{}", "╰───╯".repeat(length), padding),
+        ]
+    }
+}
+
+impl fmt::Display for Word {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for letter in &self.letters {
+            write!(f, "{}", letter.letter())?;
+        }
+
+        Ok(())
     }
 }
 
@@ -244,4 +530,91 @@ mod tests {
 
         assert!(correct_error);
     }
+
+    #[test]
+    /// Checks if the constructor scores guesses against right words that aren't 5 letters long
+    fn constructor_works_with_other_lengths() {
+        let word_list = vec![String::from("rust"), String::from("dust")];
+
+        let word_struct = Word::new(String::from("dust"), &String::from("rust"), &word_list);
+
+        assert!(word_struct.unwrap().plain() == "d[U][S][T]");
+    }
+
+    #[test]
+    /// Checks if plain text notation is read back into the same guess, and broken notation is
+    /// rejected
+    fn from_plain_reverses_plain() {
+        let word = Word::from_plain("[C](R)ane").unwrap();
+
+        assert!(word.to_string() == "crane");
+        assert!(word.plain() == "[C](R)ane");
+
+        assert!(Word::from_plain("[C(R)ane").is_none());
+        assert!(Word::from_plain("cr4ne").is_none());
+        assert!(Word::from_plain("").is_none());
+    }
+
+    #[test]
+    /// Checks if hard mode catches guesses that drop a revealed hint
+    fn hints_used_by_catches_missing_hints() {
+        let word_list = vec![
+            String::from("crane"),
+            String::from("cigar"),
+            String::from("moist"),
+            String::from("clear"),
+        ];
+
+        // C is in the right place and R is in the wrong place
+        let word_struct = Word::new(String::from("crane"), &String::from("cigar"), &word_list);
+        let word_struct = word_struct.unwrap();
+
+        assert!(word_struct.hints_used_by("clear"));
+        assert!(!word_struct.hints_used_by("moist"));
+    }
+
+    #[test]
+    /// Checks if the constructor accepts guesses regardless of case, and scores them the same as
+    /// a lowercase guess
+    fn constructor_ignores_case() {
+        // Wordlist to guess from, case folded as it is when the list is loaded
+        let word_list = vec![
+            String::from("crane"),
+            String::from("guess"),
+            String::from("juice"),
+            String::from("spree"),
+        ];
+
+        let word_struct = Word::new(String::from("CRANE"), &String::from("CRANE"), &word_list);
+
+        assert!(word_struct.is_ok());
+
+        let word_struct = word_struct.unwrap();
+
+        assert!(word_struct.letters[0].letter() == &'c');
+    }
+
+    #[test]
+    /// Checks if a word scored by lying rules marks exactly one letter wrongly, and reveals the
+    /// true states afterwards
+    fn fibble_marks_one_letter_wrongly() {
+        let word_list = vec![String::from("cigar"), String::from("crane")];
+
+        let truth = Word::new(String::from("crane"), "cigar", &word_list).unwrap();
+        let word = Word::new(String::from("crane"), "cigar", &word_list)
+            .unwrap()
+            .with_scoring(Scoring::Fibble);
+
+        let lie = word.lie().unwrap();
+        let differing: Vec<usize> = (0..5)
+            .filter(|&i| word.letters[i].state() != truth.letters[i].state())
+            .collect();
+
+        assert!(differing == vec![lie]);
+        assert!(word.revealed().plain() == truth.plain());
+
+        // The answer would have got this feedback with exactly one letter marked wrongly
+        assert!(word.hints_used_by("cigar"));
+        assert!(!word.hints_used_by("crane"));
+    }
 }
@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::solver;
+
+/// The number of entries shown for each ranking in the report
+pub const TOP: usize = 5;
+
+/// The letters counted as vowels when working out the pattern of a word
+const VOWELS: &[char] = &['a', 'e', 'i', 'o', 'u'];
+
+/// The number of words, taken from the best by letter frequency, that are rated by the
+/// information they give. Rating every word against every other word takes too long for big lists
+const INFORMATION_SHORTLIST: usize = 100;
+
+/// Struct to represent what a word list is made of, to help curate lists and pick openers
+#[derive(Debug)]
+pub struct WordStats {
+    /// The number of words in the list
+    pub words: usize,
+    /// The number of times each letter is used, most used first
+    pub letters: Vec<(char, usize)>,
+    /// The number of times each letter is used at each position, most used first
+    pub positions: Vec<Vec<(char, usize)>>,
+    /// The number of words with each pattern of consonants and vowels, such as CVCVC, most common
+    /// first
+    pub patterns: Vec<(String, usize)>,
+    /// The number of words that use a letter more than once
+    pub repeated: usize,
+    /// The number of words with the same letter twice in a row
+    pub doubled: usize,
+    /// The number of words that use each letter more than once, most common first
+    pub repeated_letters: Vec<(char, usize)>,
+    /// The best starting words by how common their letters are, with their scores
+    pub best_by_frequency: Vec<(String, f64)>,
+    /// The best starting words by how common their letters are in the places they are used
+    pub best_by_position: Vec<(String, f64)>,
+    /// The best starting words by the information they are expected to give, in bits
+    pub best_by_information: Vec<(String, f64)>,
+}
+
+impl WordStats {
+    /// Returns the stats of a word list
+    ///
+    /// Parameters
+    /// words:  The words in the list, already case folded and all the same length
+    pub fn new(words: &[String]) -> WordStats {
+        let mut letters: HashMap<char, usize> = HashMap::new();
+        let mut positions: Vec<HashMap<char, usize>> = Vec::new();
+        let mut patterns: HashMap<String, usize> = HashMap::new();
+        let mut repeated_letters: HashMap<char, usize> = HashMap::new();
+        let mut repeated = 0;
+        let mut doubled = 0;
+
+        for word in words {
+            let chars: Vec<char> = word.chars().collect();
+
+            if positions.len() < chars.len() {
+                positions.resize(chars.len(), HashMap::new());
+            }
+
+            let mut counts: HashMap<char, usize> = HashMap::new();
+
+            for (i, letter) in chars.iter().enumerate() {
+                *letters.entry(*letter).or_insert(0) += 1;
+                *positions[i].entry(*letter).or_insert(0) += 1;
+                *counts.entry(*letter).or_insert(0) += 1;
+            }
+
+            *patterns.entry(pattern(word)).or_insert(0) += 1;
+
+            let repeats: Vec<char> = counts
+                .iter()
+                .filter(|(_, &count)| count > 1)
+                .map(|(letter, _)| *letter)
+                .collect();
+
+            if !repeats.is_empty() {
+                repeated += 1;
+            }
+
+            for letter in repeats {
+                *repeated_letters.entry(letter).or_insert(0) += 1;
+            }
+
+            if chars.windows(2).any(|pair| pair[0] == pair[1]) {
+                doubled += 1;
+            }
+        }
+
+        let best_by_frequency = best_words(words, |word| solver::frequency_score(word, &letters));
+        let best_by_position = best_words(words, |word| position_score(word, &positions));
+
+        // Only the words with the most common letters are rated by information
+        let shortlist: Vec<String> = rank(words, |word| solver::frequency_score(word, &letters))
+            .into_iter()
+            .take(INFORMATION_SHORTLIST)
+            .map(|(word, _)| word)
+            .collect();
+        let best_by_information = best_words(&shortlist, |word| solver::expected_bits(word, words));
+
+        WordStats {
+            words: words.len(),
+            letters: sorted(letters),
+            positions: positions.into_iter().map(sorted).collect(),
+            patterns: sorted(patterns),
+            repeated,
+            doubled,
+            repeated_letters: sorted(repeated_letters),
+            best_by_frequency,
+            best_by_position,
+            best_by_information,
+        }
+    }
+
+    /// Returns the total number of letters in every word
+    pub fn total_letters(&self) -> usize {
+        self.letters.iter().map(|(_, count)| count).sum()
+    }
+}
+
+impl fmt::Display for WordStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let letters = self.total_letters();
+
+        writeln!(f, "{} words", self.words)?;
+
+        writeln!(f, "\nLetter frequency")?;
+        for row in self.letters.chunks(6) {
+            let entries: Vec<String> = row
+                .iter()
+                .map(|(letter, count)| {
+                    format!(
+                        "{} {:>4.1}%",
+                        letter.to_uppercase(),
+                        percent(*count, letters)
+                    )
+                })
+                .collect();
+
+            writeln!(f, "  {}", entries.join("   "))?;
+        }
+
+        writeln!(f, "\nMost common letters in each position")?;
+        for (i, counts) in self.positions.iter().enumerate() {
+            let entries: Vec<String> = counts
+                .iter()
+                .take(TOP)
+                .map(|(letter, count)| {
+                    format!(
+                        "{} {:>4.1}%",
+                        letter.to_uppercase(),
+                        percent(*count, self.words)
+                    )
+                })
+                .collect();
+
+            writeln!(f, "  {:>2}: {}", i + 1, entries.join("   "))?;
+        }
+
+        writeln!(f, "\nMost common patterns (C for consonant, V for vowel)")?;
+        for (pattern, count) in self.patterns.iter().take(TOP) {
+            writeln!(
+                f,
+                "  {}  {:>5}  {:>4.1}%",
+                pattern,
+                count,
+                percent(*count, self.words)
+            )?;
+        }
+
+        writeln!(f, "\nDouble letters")?;
+        writeln!(
+            f,
+            "  {} words use a letter more than once ({:.1}%)",
+            self.repeated,
+            percent(self.repeated, self.words)
+        )?;
+        writeln!(
+            f,
+            "  {} words have the same letter twice in a row ({:.1}%)",
+            self.doubled,
+            percent(self.doubled, self.words)
+        )?;
+
+        let repeats: Vec<String> = self
+            .repeated_letters
+            .iter()
+            .take(TOP)
+            .map(|(letter, count)| format!("{} {}", letter.to_uppercase(), count))
+            .collect();
+
+        if !repeats.is_empty() {
+            writeln!(f, "  Most repeated: {}", repeats.join(", "))?;
+        }
+
+        let rankings = [
+            ("letter frequency", &self.best_by_frequency),
+            ("letter frequency in each position", &self.best_by_position),
+            ("expected information in bits", &self.best_by_information),
+        ];
+
+        for (name, best) in rankings {
+            writeln!(f, "\nBest starting words by {}", name)?;
+
+            for (word, score) in best {
+                writeln!(f, "  {}  {:.2}", word.to_uppercase(), score)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the pattern of consonants and vowels in a word, such as CVCVC for "cigar"
+///
+/// Parameters
+/// word:   The word to get the pattern of, already case folded
+pub fn pattern(word: &str) -> String {
+    word.chars()
+        .map(|letter| match VOWELS.contains(&letter) {
+            true => 'V',
+            false => 'C',
+        })
+        .collect()
+}
+
+/// Returns how common the letters of a word are in the places the word uses them, as a share of
+/// the words in the list
+///
+/// Parameters
+/// word:       The word to score
+/// positions:  The number of times each letter is used at each position in the list
+fn position_score(word: &str, positions: &[HashMap<char, usize>]) -> f64 {
+    word.chars()
+        .zip(positions)
+        .map(|(letter, counts)| {
+            let total: usize = counts.values().sum();
+            percent(counts.get(&letter).copied().unwrap_or(0), total)
+        })
+        .sum()
+}
+
+/// Returns every word with its score, best first. Words with the same score are kept in
+/// alphabetical order
+///
+/// Parameters
+/// words:  The words to rank
+/// score:  How to score each word
+fn rank(words: &[String], score: impl Fn(&str) -> f64) -> Vec<(String, f64)> {
+    let mut ranked: Vec<(String, f64)> = words
+        .iter()
+        .map(|word| (word.clone(), score(word)))
+        .collect();
+
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    ranked
+}
+
+/// Returns the best few words with their scores
+///
+/// Parameters
+/// words:  The words to rank
+/// score:  How to score each word
+fn best_words(words: &[String], score: impl Fn(&str) -> f64) -> Vec<(String, f64)> {
+    let mut ranked = rank(words, score);
+    ranked.truncate(TOP);
+
+    ranked
+}
+
+/// Returns the entries of a count, most common first. Entries with the same count are kept in
+/// order of their keys
+///
+/// Parameters
+/// counts: The count to sort
+fn sorted<T: Ord>(counts: HashMap<T, usize>) -> Vec<(T, usize)> {
+    let mut entries: Vec<(T, usize)> = counts.into_iter().collect();
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    entries
+}
+
+/// Returns a count as a percentage of a total
+///
+/// Parameters
+/// count:  The count
+/// total:  The total the count is part of
+fn percent(count: usize, total: usize) -> f64 {
+    match total {
+        0 => 0.0,
+        total => count as f64 * 100.0 / total as f64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a small word list to take the stats of
+    fn test_words() -> Vec<String> {
+        ["cigar", "crane", "abbey", "sissy", "eerie"]
+            .iter()
+            .map(|word| word.to_string())
+            .collect()
+    }
+
+    #[test]
+    /// Checks if letters are counted overall and in each position, most common first
+    fn letters_are_counted() {
+        let stats = WordStats::new(&test_words());
+
+        assert!(stats.words == 5);
+        assert!(stats.total_letters() == 25);
+        assert!(stats.letters[0] == ('e', 5));
+        assert!(stats.positions[0][0] == ('c', 2));
+        assert!(stats.positions.len() == 5);
+    }
+
+    #[test]
+    /// Checks if repeated and doubled letters are counted once for each word
+    fn double_letters_are_counted() {
+        let stats = WordStats::new(&test_words());
+
+        // "abbey", "sissy" and "eerie" repeat a letter, and all three have it twice in a row
+        assert!(stats.repeated == 3);
+        assert!(stats.doubled == 3);
+        assert!(stats.repeated_letters.contains(&('s', 1)));
+        assert!(stats.repeated_letters.contains(&('e', 1)));
+    }
+
+    #[test]
+    /// Checks if words are given the right pattern of consonants and vowels
+    fn patterns_follow_vowels() {
+        assert!(pattern("cigar") == "CVCVC");
+        assert!(pattern("eerie") == "VVCVV");
+
+        let stats = WordStats::new(&test_words());
+        assert!(stats.patterns.contains(&(String::from("CVCVC"), 1)));
+        assert!(stats.best_by_information.len() == TOP);
+    }
+}